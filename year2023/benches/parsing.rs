@@ -0,0 +1,43 @@
+//! Times the `&str` code of `year2023::compare` against its byte scanner replacement on
+//! the examples, repeated until they are about the size of a real input.
+//!
+//! `cargo bench --bench parsing --features compare`
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use year2023::compare::CASES;
+
+/// Lines per generated input, real inputs have up to about 1000. Day 6 gets as many
+/// numbers instead.
+const LINES: usize = 1000;
+const RUNS: u32 = 200;
+
+fn time<T: ?Sized>(input: &T, f: impl Fn(&T) -> usize) -> Duration {
+    // once to warm up
+    black_box(f(black_box(input)));
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(f(black_box(input)));
+    }
+    return start.elapsed() / RUNS;
+}
+
+fn main() {
+    println!("{:<14} {:>12} {:>12} {:>8}", "", "&str", "&[u8]", "speedup");
+    for case in &CASES {
+        let lines = case.example.lines().count();
+        let input = (case.grow)(case.example, LINES.div_ceil(lines));
+        let before = time(input.as_str(), case.before);
+        let after = time(input.as_bytes(), case.after);
+        println!(
+            "{:<14} {:>12.2?} {:>12.2?} {:>7.2}x",
+            case.name,
+            before,
+            after,
+            before.as_secs_f64() / after.as_secs_f64()
+        );
+    }
+}
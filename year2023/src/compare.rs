@@ -0,0 +1,182 @@
+//! The `&str` code the byte scanner of `helpers::scan` replaced, side by side with its
+//! replacement, for `benches/parsing.rs`. Only built with the `compare` feature.
+
+use crate::{day01, day04, day06, day09};
+
+/// One piece of code before and after the move to bytes, both reduced to a number so the
+/// results can be compared.
+pub struct Case {
+    pub name: &'static str,
+    pub example: &'static str,
+    pub before: fn(&str) -> usize,
+    pub after: fn(&[u8]) -> usize,
+    /// Repeats the example `n` times, somehow.
+    pub grow: fn(&str, usize) -> String,
+}
+
+pub const CASES: [Case; 5] = [
+    Case {
+        name: "day01 part 1",
+        example: "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n",
+        before: day01_part1,
+        after: |input| day01::solve_part1(input) as usize,
+        grow: str::repeat,
+    },
+    Case {
+        name: "day01 part 2",
+        example: "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n",
+        before: day01_part2,
+        after: day01::solve_part2,
+        grow: str::repeat,
+    },
+    Case {
+        name: "day04 parse",
+        example: "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n",
+        before: |input| day04_parse(input).len(),
+        after: |input| day04::parse(input).unwrap().len(),
+        grow: str::repeat,
+    },
+    Case {
+        name: "day06 parse",
+        example: "Time:      7  15   30\nDistance:  9  40  200\n",
+        before: |input| day06_parse(input).len(),
+        after: |input| day06::parser_part1(input).unwrap().len(),
+        grow: grow_lines,
+    },
+    Case {
+        name: "day09 parse",
+        example: "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n",
+        before: |input| day09_parse(input).len(),
+        after: |input| day09::parse(input).unwrap().len(),
+        grow: str::repeat,
+    },
+];
+
+/// There are only ever two races, so every line gets longer instead.
+fn grow_lines(example: &str, n: usize) -> String {
+    return example
+        .lines()
+        .map(|line| {
+            let (label, numbers) = line.split_once(':').unwrap();
+            return format!("{label}:{}\n", numbers.repeat(n));
+        })
+        .collect();
+}
+
+fn day01_part1(input: &str) -> usize {
+    return input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .filter_map(|c| c.to_digit(10))
+                .fold(0, |acc, n| (if acc == 0 { n } else { acc / 10 }) * 10 + n)
+        })
+        .sum::<u32>() as usize;
+}
+
+fn day01_part2(input: &str) -> usize {
+    return input
+        .lines()
+        .map(|line| {
+            ("----".to_owned() + line)
+                .chars()
+                .collect::<Vec<char>>()
+                .windows(5)
+                .filter_map(|c| match c {
+                    d if d[4] == '0' => None,
+                    d if d[4].is_ascii_digit() => d[4].to_digit(10),
+                    d if d[2..5] == ['o', 'n', 'e'] => Some(1),
+                    d if d[2..5] == ['t', 'w', 'o'] => Some(2),
+                    d if d[2..5] == ['s', 'i', 'x'] => Some(6),
+                    d if d[1..5] == ['f', 'o', 'u', 'r'] => Some(4),
+                    d if d[1..5] == ['f', 'i', 'v', 'e'] => Some(5),
+                    d if d[1..5] == ['n', 'i', 'n', 'e'] => Some(9),
+                    d if d == ['t', 'h', 'r', 'e', 'e'] => Some(3),
+                    d if d == ['s', 'e', 'v', 'e', 'n'] => Some(7),
+                    d if d == ['e', 'i', 'g', 'h', 't'] => Some(8),
+                    _ => None,
+                })
+                .fold(0, |acc, n| (if acc == 0 { n } else { acc / 10 }) * 10 + n)
+        })
+        .map(|x| x as usize)
+        .sum();
+}
+
+fn day04_parse(input: &str) -> Vec<(Vec<usize>, Vec<usize>)> {
+    return input
+        .lines()
+        .map(|line| {
+            let (w, d) = line.split_once(':').unwrap().1.split_once('|').unwrap();
+            let numbers = |s: &str| {
+                return s
+                    .trim()
+                    .split_ascii_whitespace()
+                    .map(|s| s.parse().unwrap())
+                    .collect();
+            };
+            return (numbers(w), numbers(d));
+        })
+        .collect();
+}
+
+fn day06_parse(input: &str) -> Vec<(i64, i64)> {
+    let numbers = |line: &str| {
+        return line
+            .split_once(':')
+            .unwrap()
+            .1
+            .trim()
+            .split_ascii_whitespace()
+            .map(str::parse)
+            .map(Result::unwrap)
+            .collect::<Vec<i64>>();
+    };
+    let (times, distances) = input.split_once('\n').unwrap();
+    return numbers(times).into_iter().zip(numbers(distances)).collect();
+}
+
+fn day09_parse(input: &str) -> Vec<Vec<i64>> {
+    return input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(str::parse)
+                .map(Result::unwrap)
+                .collect()
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cases_agree() {
+        for case in &CASES {
+            assert_eq!(
+                (case.before)(case.example),
+                (case.after)(case.example.as_bytes()),
+                "{}",
+                case.name
+            );
+        }
+        assert_eq!(281, (CASES[1].before)(CASES[1].example));
+        for case in &CASES {
+            let input = (case.grow)(case.example, 3);
+            assert_eq!(
+                3 * (case.before)(case.example),
+                (case.before)(&input),
+                "{}",
+                case.name
+            );
+            assert_eq!((case.before)(&input), (case.after)(input.as_bytes()));
+        }
+        // the parsed values themselves, where the types allow comparing them
+        let example = CASES[4].example;
+        assert_eq!(
+            day09_parse(example),
+            day09::parse(example.as_bytes()).unwrap()
+        );
+    }
+}
@@ -1,44 +1,194 @@
+mod words {
+    use std::collections::{HashMap, VecDeque};
+
+    const DIGITS: [(&str, u32); 9] = [
+        ("1", 1),
+        ("2", 2),
+        ("3", 3),
+        ("4", 4),
+        ("5", 5),
+        ("6", 6),
+        ("7", 7),
+        ("8", 8),
+        ("9", 9),
+    ];
+
+    pub const ENGLISH: [(&str, u32); 9] = [
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("six", 6),
+        ("seven", 7),
+        ("eight", 8),
+        ("nine", 9),
+    ];
+
+    pub const GERMAN: [(&str, u32); 9] = [
+        ("eins", 1),
+        ("zwei", 2),
+        ("drei", 3),
+        ("vier", 4),
+        ("fünf", 5),
+        ("sechs", 6),
+        ("sieben", 7),
+        ("acht", 8),
+        ("neun", 9),
+    ];
+
+    /// A node of the trie the automaton is built from.
+    #[derive(Debug, Clone, Default)]
+    struct Node {
+        next: HashMap<u8, usize>,
+        fail: usize,
+        /// (length in bytes, digit) of every word ending here, including via `fail`.
+        outputs: Vec<(usize, u32)>,
+    }
+
+    /// Aho–Corasick automaton finding digits and number words, overlapping ones included.
+    #[derive(Debug, Clone)]
+    pub struct DigitMatcher {
+        /// The state after each byte, with the fail links already followed.
+        goto: Vec<[u32; 256]>,
+        outputs: Vec<Vec<(usize, u32)>>,
+    }
+
+    impl DigitMatcher {
+        /// Matches the digits `1` to `9` and every word of `words`.
+        pub fn new(words: &[(&str, u32)]) -> Self {
+            let mut nodes = vec![Node::default()];
+            for &(word, digit) in DIGITS.iter().chain(words) {
+                let mut current = 0;
+                for &byte in word.as_bytes() {
+                    current = match nodes[current].next.get(&byte) {
+                        Some(&next) => next,
+                        None => {
+                            nodes.push(Node::default());
+                            let next = nodes.len() - 1;
+                            nodes[current].next.insert(byte, next);
+                            next
+                        }
+                    };
+                }
+                nodes[current].outputs.push((word.len(), digit));
+            }
+
+            // breadth first, so every fail link points to an already finished node
+            let mut order = vec![0];
+            let mut queue: VecDeque<usize> = nodes[0].next.values().copied().collect();
+            while let Some(node) = queue.pop_front() {
+                order.push(node);
+                let edges: Vec<(u8, usize)> =
+                    nodes[node].next.iter().map(|(&b, &n)| (b, n)).collect();
+                for (byte, child) in edges {
+                    let mut fail = nodes[node].fail;
+                    while fail != 0 && !nodes[fail].next.contains_key(&byte) {
+                        fail = nodes[fail].fail;
+                    }
+                    let fail = nodes[fail]
+                        .next
+                        .get(&byte)
+                        .copied()
+                        .filter(|&f| f != child)
+                        .unwrap_or(0);
+                    nodes[child].fail = fail;
+                    let inherited = nodes[fail].outputs.clone();
+                    nodes[child].outputs.extend(inherited);
+                    queue.push_back(child);
+                }
+            }
+
+            // a byte without an edge goes where the fail node would go, which is done already
+            let mut goto = vec![[0_u32; 256]; nodes.len()];
+            for &node in &order {
+                let fail = goto[nodes[node].fail];
+                goto[node] = std::array::from_fn(|byte| {
+                    return nodes[node]
+                        .next
+                        .get(&(byte as u8))
+                        .map_or(fail[byte], |&next| next as u32);
+                });
+            }
+            let outputs = nodes.into_iter().map(|node| node.outputs).collect();
+            return Self { goto, outputs };
+        }
+
+        pub fn english() -> Self {
+            return Self::new(&ENGLISH);
+        }
+
+        pub fn german() -> Self {
+            return Self::new(&GERMAN);
+        }
+
+        /// Every match as `(start byte, digit)`, ordered by where the matches end.
+        pub fn matches<'a>(&'a self, line: &'a [u8]) -> impl Iterator<Item = (usize, u32)> + 'a {
+            let mut current = 0;
+            return line.iter().enumerate().flat_map(move |(i, &byte)| {
+                current = self.goto[current][byte as usize] as usize;
+                return self.outputs[current]
+                    .iter()
+                    .map(move |&(len, digit)| (i + 1 - len, digit));
+            });
+        }
+
+        /// The first digit followed by the last one, `None` without any digit.
+        pub fn calibration_value(&self, line: &[u8]) -> Option<u32> {
+            let mut first: Option<(usize, u32)> = None;
+            let mut last: Option<(usize, u32)> = None;
+            for (start, digit) in self.matches(line) {
+                if first.is_none_or(|(s, _)| start < s) {
+                    first = Some((start, digit));
+                }
+                if last.is_none_or(|(s, _)| start >= s) {
+                    last = Some((start, digit));
+                }
+            }
+            return Some(first?.1 * 10 + last?.1);
+        }
+    }
+
+    impl Default for DigitMatcher {
+        fn default() -> Self {
+            return Self::english();
+        }
+    }
+}
+
+use crate::helpers::scan::lines;
+use words::DigitMatcher;
+
 #[aoc(day1, part1)]
-pub fn solve_part1(input: &str) -> u32 {
-    return input
-        .lines()
+pub fn solve_part1(input: &[u8]) -> u32 {
+    return lines(input)
         .map(|line| {
-            line.chars()
-                .filter_map(|c| c.to_digit(10))
+            line.iter()
+                .filter(|b| b.is_ascii_digit())
+                .map(|b| u32::from(b - b'0'))
                 .fold(0, |acc, n| (if acc == 0 { n } else { acc / 10 }) * 10 + n)
         })
         .sum();
 }
 
-#[aoc(day1, part2)]
-fn solve_part2(input: &str) -> usize {
-    return input
-        .lines()
-        .map(|line| {
-            ("----".to_owned() + line)
-                .chars()
-                .collect::<Vec<char>>()
-                .windows(5)
-                .filter_map(|c| match c {
-                    d if d[4] == '0' => None,
-                    d if d[4].is_ascii_digit() => d[4].to_digit(10),
-                    d if d[2..5] == ['o', 'n', 'e'] => Some(1),
-                    d if d[2..5] == ['t', 'w', 'o'] => Some(2),
-                    d if d[2..5] == ['s', 'i', 'x'] => Some(6),
-                    d if d[1..5] == ['f', 'o', 'u', 'r'] => Some(4),
-                    d if d[1..5] == ['f', 'i', 'v', 'e'] => Some(5),
-                    d if d[1..5] == ['n', 'i', 'n', 'e'] => Some(9),
-                    d if d == ['t', 'h', 'r', 'e', 'e'] => Some(3),
-                    d if d == ['s', 'e', 'v', 'e', 'n'] => Some(7),
-                    d if d == ['e', 'i', 'g', 'h', 't'] => Some(8),
-                    _ => None,
-                })
-                .fold(0, |acc, n| (if acc == 0 { n } else { acc / 10 }) * 10 + n)
-        })
+fn calibration_sum(input: &[u8], matcher: &DigitMatcher) -> usize {
+    return lines(input)
+        .filter_map(|line| matcher.calibration_value(line))
         .map(|x| x as usize)
         .sum();
 }
 
+#[aoc(day1, part2)]
+pub fn solve_part2(input: &[u8]) -> usize {
+    return calibration_sum(input, &DigitMatcher::default());
+}
+
+/// Part 2 for puzzles with the number words in German.
+#[aoc(day1, part2, german)]
+fn solve_part2_german(input: &[u8]) -> usize {
+    return calibration_sum(input, &DigitMatcher::german());
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,11 +198,38 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(142, solve_part1(EXAMPLE_1));
+        assert_eq!(142, solve_part1(EXAMPLE_1.as_bytes()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(281, solve_part2(EXAMPLE_2));
+        assert_eq!(281, solve_part2(EXAMPLE_2.as_bytes()));
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let matcher = DigitMatcher::english();
+        assert_eq!(
+            vec![(0, 8), (4, 2)],
+            matcher.matches(b"eightwo").collect::<Vec<_>>()
+        );
+        assert_eq!(Some(83), matcher.calibration_value(b"xeightwothree"));
+        assert_eq!(Some(18), matcher.calibration_value(b"oneight0"));
+        assert_eq!(None, matcher.calibration_value(b"zero"));
+        // "nine" inside "ninine", found again after the first attempt fails
+        assert_eq!(Some(99), matcher.calibration_value(b"ninine"));
+    }
+
+    #[test]
+    fn test_german() {
+        let matcher = DigitMatcher::german();
+        assert_eq!(Some(21), matcher.calibration_value(b"zweins"));
+        assert_eq!(
+            Some(57),
+            matcher.calibration_value("xfünfachtsiebenx".as_bytes())
+        );
+        assert_eq!(Some(33), matcher.calibration_value(b"dreight"));
+        assert_eq!(None, matcher.calibration_value(b"two"));
+        assert_eq!(21 + 98 + 44, solve_part2_german(b"zweins\nneunacht\n4"));
     }
 }
@@ -1,72 +1,260 @@
-struct Draw {
-    red: usize,
-    green: usize,
-    blue: usize,
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::helpers::math::gcd;
+
+/// The bag of the puzzle, in the same format as a draw.
+pub const BAG: &str = "12 red, 13 green, 14 blue";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Something that isn't `<count> <colour>`.
+    Cubes(String),
+    /// A line that isn't `Game <id>: <draws>`.
+    Game(String),
 }
 
-pub struct Game {
-    id: usize,
-    draws: Vec<Draw>,
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ParseError::Cubes(s) => write!(f, "expected \"<count> <colour>\", got {:?}", s),
+            ParseError::Game(s) => write!(f, "expected \"Game <id>: <draws>\", got {:?}", s),
+        };
+    }
 }
 
-fn parse_cubes(input: &str) -> Draw {
-    let mut red: usize = 0;
-    let mut green: usize = 0;
-    let mut blue: usize = 0;
+impl Error for ParseError {}
 
-    input.split(',').for_each(|s| {
-        if s.contains("red") {
-            red = s.strip_suffix("red").unwrap().trim().parse().unwrap_or(0);
-        }
-        if s.contains("green") {
-            green = s.strip_suffix("green").unwrap().trim().parse().unwrap_or(0);
+/// Cubes by colour, missing colours count as zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes(BTreeMap<String, usize>);
+
+impl Cubes {
+    pub fn get(&self, colour: &str) -> usize {
+        return self.0.get(colour).copied().unwrap_or(0);
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        return self.0.keys().map(String::as_str);
+    }
+
+    /// Whether these cubes could have come out of `bag`.
+    pub fn fits(&self, bag: &Cubes) -> bool {
+        return self
+            .0
+            .iter()
+            .all(|(colour, &count)| count <= bag.get(colour));
+    }
+
+    /// The larger count of each colour.
+    pub fn max(mut self, other: &Cubes) -> Cubes {
+        for (colour, &count) in &other.0 {
+            let entry = self.0.entry(colour.clone()).or_default();
+            *entry = count.max(*entry);
         }
-        if s.contains("blue") {
-            blue = s.strip_suffix("blue").unwrap().trim().parse().unwrap_or(0);
+        return self;
+    }
+}
+
+impl FromStr for Cubes {
+    type Err = ParseError;
+
+    /// Parses `3 blue, 4 red`. A colour named twice is counted twice.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cubes = BTreeMap::new();
+        for part in s.split(',') {
+            let error = || ParseError::Cubes(part.trim().to_owned());
+            let (count, colour) = part.trim().split_once(' ').ok_or_else(error)?;
+            let count: usize = count.parse().map_err(|_| error())?;
+            let colour = colour.trim();
+            if colour.is_empty() || colour.contains(char::is_whitespace) {
+                return Err(error());
+            }
+            let total: &mut usize = cubes.entry(colour.to_owned()).or_default();
+            *total = total.checked_add(count).ok_or_else(error)?;
         }
-    });
-    return Draw { red, green, blue };
+        return Ok(Cubes(cubes));
+    }
 }
 
-fn parse_line(input: &str) -> Game {
-    let (a, b) = input.split_once(':').unwrap();
-    return Game {
-        id: a.strip_prefix("Game ").unwrap().parse::<usize>().unwrap(),
-        draws: b.split(';').map(parse_cubes).collect::<Vec<Draw>>(),
-    };
+pub struct Game {
+    id: usize,
+    draws: Vec<Cubes>,
+}
+
+impl Game {
+    /// The fewest cubes of each colour that make this game possible.
+    fn minimal_bag(&self) -> Cubes {
+        return self.draws.iter().fold(Cubes::default(), Cubes::max);
+    }
+}
+
+fn parse_line(input: &str) -> Result<Game, ParseError> {
+    let error = || ParseError::Game(input.to_owned());
+    let (a, b) = input.split_once(':').ok_or_else(error)?;
+    let id = a.strip_prefix("Game ").and_then(|id| id.parse().ok());
+    return Ok(Game {
+        id: id.ok_or_else(error)?,
+        draws: b.split(';').map(str::parse).collect::<Result<_, _>>()?,
+    });
 }
 
 #[aoc_generator(day2)]
-fn parse(input: &str) -> Vec<self::Game> {
-    input.lines().map(parse_line).collect()
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    return input.lines().map(parse_line).collect();
 }
 
-#[aoc(day2, part1)]
-pub fn solve_part1(data: &[Game]) -> usize {
+/// The sum of the ids of the games possible with `bag`.
+pub fn solve_with_bag(data: &[Game], bag: &Cubes) -> usize {
     return data
         .iter()
-        .filter(|game| {
-            game.draws
-                .iter()
-                .all(|draw| draw.red <= 12 && draw.green <= 13 && draw.blue <= 14)
-        })
+        .filter(|game| game.draws.iter().all(|draw| draw.fits(bag)))
         .map(|game| game.id)
         .sum();
 }
 
+/// `solve_with_bag` with the bag of the puzzle.
+#[aoc(day2, part1)]
+pub fn solve_part1(data: &[Game]) -> usize {
+    return solve_with_bag(data, &BAG.parse().unwrap());
+}
+
+/// The product of the minimal counts over every colour of the input. A game never showing
+/// one of them has a power of zero.
 #[aoc(day2, part2)]
 fn solve_part2(data: &[Game]) -> usize {
+    let colours: BTreeSet<&str> = data
+        .iter()
+        .flat_map(|game| game.draws.iter().flat_map(Cubes::colours))
+        .collect();
     return data
         .iter()
         .map(|game| {
-            let red = game.draws.iter().map(|draw| draw.red).max().unwrap();
-            let green = game.draws.iter().map(|draw| draw.green).max().unwrap();
-            let blue = game.draws.iter().map(|draw| draw.blue).max().unwrap();
-            return red * green * blue;
+            let bag = game.minimal_bag();
+            return colours
+                .iter()
+                .map(|colour| bag.get(colour))
+                .product::<usize>();
         })
         .sum();
 }
 
+/// The colours of the puzzle, for bags written as `(r, g, b)`.
+pub const RGB: [&str; 3] = ["red", "green", "blue"];
+
+/// Whether `cubes` fit into a bag holding nothing but `colours`.
+fn only_of(cubes: &Cubes, colours: &[&str]) -> bool {
+    return cubes
+        .colours()
+        .all(|colour| colours.contains(&colour) || cubes.get(colour) == 0);
+}
+
+/// Adds the bags holding `minimum` plus at most `slack` cubes, after the `counts` chosen so far.
+fn fill(
+    bags: &mut Vec<Cubes>,
+    minimum: &Cubes,
+    colours: &[&str],
+    counts: &mut Vec<usize>,
+    slack: usize,
+) {
+    let Some(colour) = colours.get(counts.len()) else {
+        let bag = colours.iter().zip(counts.iter());
+        bags.push(Cubes(bag.map(|(c, &n)| (c.to_string(), n)).collect()));
+        return;
+    };
+    for extra in 0..=slack {
+        counts.push(minimum.get(colour) + extra);
+        fill(bags, minimum, colours, counts, slack - extra);
+        counts.pop();
+    }
+}
+
+/// Every bag of `colours` with at most `budget` cubes that holds at least `minimum`.
+fn bags_containing(minimum: &Cubes, colours: &[&str], budget: usize) -> Vec<Cubes> {
+    let mut bags = Vec::new();
+    let required: usize = colours.iter().map(|colour| minimum.get(colour)).sum();
+    if only_of(minimum, colours) && required <= budget {
+        fill(
+            &mut bags,
+            minimum,
+            colours,
+            &mut Vec::new(),
+            budget - required,
+        );
+    }
+    return bags;
+}
+
+/// The bags of `colours` with at most `budget` cubes in which `game` is possible, `None` if
+/// there is no budget and so no end to them.
+pub fn consistent_bags(game: &Game, colours: &[&str], budget: Option<usize>) -> Option<Vec<Cubes>> {
+    let minimum = game.minimal_bag();
+    let budget = match budget {
+        Some(budget) => budget,
+        // there is no bag to grow
+        None if colours.is_empty() || !only_of(&minimum, colours) => 0,
+        None => return None,
+    };
+    return Some(bags_containing(&minimum, colours, budget));
+}
+
+/// How many bags `consistent_bags` finds, `None` if there is no end to them or their
+/// number doesn't fit.
+pub fn count_consistent_bags(game: &Game, colours: &[&str], budget: Option<usize>) -> Option<u128> {
+    let minimum = game.minimal_bag();
+    if !only_of(&minimum, colours) {
+        return Some(0);
+    }
+    if colours.is_empty() {
+        return Some(1);
+    }
+    let required: usize = colours.iter().map(|colour| minimum.get(colour)).sum();
+    let Some(slack) = budget?.checked_sub(required) else {
+        return Some(0);
+    };
+    // stars and bars, the cubes left out being one more colour: (slack + k choose k)
+    let k = colours.len() as u128;
+    // acc * (slack + i) / i is (slack + i choose i), so i / g divides slack + i and only
+    // a count that really doesn't fit overflows
+    return (1..=k).try_fold(1_u128, |acc, i| {
+        let g = gcd(acc, i).ok()?;
+        return (acc / g).checked_mul((slack as u128 + i) / (i / g));
+    });
+}
+
+/// The ids of the games that can't have been played with `bag`.
+pub fn ruled_out_by(data: &[Game], bag: &Cubes) -> Vec<usize> {
+    return data
+        .iter()
+        .filter(|game| !game.draws.iter().all(|draw| draw.fits(bag)))
+        .map(|game| game.id)
+        .collect();
+}
+
+/// Every bag of `colours` with at most `budget` cubes, with the ids of the games ruling
+/// it out.
+pub fn rule_outs(data: &[Game], colours: &[&str], budget: usize) -> Vec<(Cubes, Vec<usize>)> {
+    return bags_containing(&Cubes::default(), colours, budget)
+        .into_iter()
+        .map(|bag| {
+            let games = ruled_out_by(data, &bag);
+            return (bag, games);
+        })
+        .collect();
+}
+
+/// The bags of `colours` with at most `budget` cubes in which every game is possible.
+pub fn consistent_with_all(data: &[Game], colours: &[&str], budget: usize) -> Vec<Cubes> {
+    let minimum = data
+        .iter()
+        .fold(Cubes::default(), |bag, game| bag.max(&game.minimal_bag()));
+    return bags_containing(&minimum, colours, budget);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,11 +263,148 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(8, solve_part1(&parse(EXAMPLE_1)));
+        let games = parse(EXAMPLE_1).unwrap();
+        assert_eq!(8, solve_with_bag(&games, &BAG.parse().unwrap()));
+        assert_eq!(8, solve_part1(&games));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(2286, solve_part2(&parse(EXAMPLE_1)));
+        assert_eq!(2286, solve_part2(&parse(EXAMPLE_1).unwrap()));
+    }
+
+    #[test]
+    fn test_cubes() {
+        let cubes: Cubes = "3 blue, 4 red, 1 blue".parse().unwrap();
+        assert_eq!(4, cubes.get("blue"));
+        assert_eq!(0, cubes.get("green"));
+        assert_eq!(vec!["blue", "red"], cubes.colours().collect::<Vec<_>>());
+        assert!(cubes.fits(&BAG.parse().unwrap()));
+        assert!(!cubes.fits(&"4 red".parse().unwrap()));
+        assert!(Cubes::default().fits(&Cubes::default()));
+
+        assert_eq!(
+            Err(ParseError::Cubes("red".to_owned())),
+            "red".parse::<Cubes>()
+        );
+        assert_eq!(
+            Err(ParseError::Cubes("x red".to_owned())),
+            "1 blue, x red".parse::<Cubes>()
+        );
+        assert_eq!(Err(ParseError::Cubes("2".to_owned())), "2".parse::<Cubes>());
+        let huge = format!("{} red", usize::MAX);
+        assert_eq!(
+            Err(ParseError::Cubes("1 red".to_owned())),
+            format!("{}, 1 red", huge).parse::<Cubes>()
+        );
+        assert_eq!(Ok(usize::MAX), huge.parse::<Cubes>().map(|c| c.get("red")));
+        assert!(parse("Game x: 1 red").is_err());
+    }
+
+    #[test]
+    fn test_other_colours() {
+        let input = "Game 1: 2 purple, 1 red; 3 purple\nGame 2: 5 purple\nGame 3: 1 red, 1 teal";
+        let games = parse(input).unwrap();
+        assert_eq!(0, solve_with_bag(&games, &BAG.parse().unwrap()));
+        assert_eq!(
+            1 + 3,
+            solve_with_bag(&games, &"4 purple, 1 red, 1 teal".parse().unwrap())
+        );
+        assert_eq!(
+            6,
+            solve_with_bag(&games, &"5 purple, 1 red, 1 teal".parse().unwrap())
+        );
+        // every game misses one of purple, red and teal
+        assert_eq!(0, solve_part2(&games));
+        let games = parse("Game 1: 2 purple, 1 red; 3 purple\nGame 2: 5 purple, 2 red").unwrap();
+        assert_eq!(3 + 5 * 2, solve_part2(&games));
+    }
+
+    fn rgb(r: usize, g: usize, b: usize) -> Cubes {
+        return format!("{} red, {} green, {} blue", r, g, b)
+            .parse()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_consistent_bags() {
+        let games = parse(EXAMPLE_1).unwrap();
+        // game 1 needs at least 4 red, 2 green and 6 blue
+        assert_eq!(
+            Some(vec![rgb(4, 2, 6)]),
+            consistent_bags(&games[0], &RGB, Some(12))
+        );
+        assert_eq!(
+            Some(vec![rgb(4, 2, 6), rgb(4, 2, 7), rgb(4, 3, 6), rgb(5, 2, 6)]),
+            consistent_bags(&games[0], &RGB, Some(13))
+        );
+        assert_eq!(Some(vec![]), consistent_bags(&games[0], &RGB, Some(11)));
+        assert_eq!(
+            Some(vec![]),
+            consistent_bags(&games[0], &["red", "blue"], None)
+        );
+        assert_eq!(
+            Some(vec![Cubes::default()]),
+            consistent_bags(&parse("Game 1: 0 red").unwrap()[0], &[], None)
+        );
+        assert_eq!(None, consistent_bags(&games[0], &RGB, None));
+
+        for budget in [0, 11, 12, 13, 20] {
+            let listed = consistent_bags(&games[0], &RGB, Some(budget))
+                .unwrap()
+                .len() as u128;
+            assert_eq!(
+                Some(listed),
+                count_consistent_bags(&games[0], &RGB, Some(budget))
+            );
+        }
+        assert_eq!(None, count_consistent_bags(&games[0], &RGB, None));
+        assert_eq!(Some(0), count_consistent_bags(&games[0], &["red"], None));
+        let colours: Vec<String> = (0..64).map(|i| format!("c{}", i)).collect();
+        let colours: Vec<&str> = colours.iter().map(String::as_str).collect();
+        let game = parse("Game 1: 1 c0").unwrap();
+        // (100 + 64 choose 64) is about 10^46
+        assert_eq!(None, count_consistent_bags(&game[0], &colours, Some(101)));
+        // no cube to spare or one of any colour
+        assert_eq!(Some(65), count_consistent_bags(&game[0], &colours, Some(2)));
+        // (max + 1) * (max + 2) overflows on the way, but not once halved
+        let game = parse("Game 1: 0 red").unwrap();
+        let max = usize::MAX as u128;
+        assert_eq!(
+            Some((1 << (usize::BITS - 1)) * (max + 2)),
+            count_consistent_bags(&game[0], &["red", "green"], Some(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn test_rule_outs() {
+        let games = parse(EXAMPLE_1).unwrap();
+        assert_eq!(vec![3, 4], ruled_out_by(&games, &BAG.parse().unwrap()));
+
+        let bags = rule_outs(&games, &RGB, 15);
+        // (15 + 3 choose 3)
+        assert_eq!(816, bags.len());
+        let (bag, ruled_out) = &bags[0];
+        assert_eq!(&rgb(0, 0, 0), bag);
+        assert_eq!(&vec![1, 2, 3, 4, 5], ruled_out);
+        // a bag is consistent with a game exactly if the game doesn't rule it out
+        for (id, game) in (1..).zip(&games) {
+            let consistent = bags.iter().filter(|(_, ids)| !ids.contains(&id));
+            assert_eq!(
+                count_consistent_bags(game, &RGB, Some(15)),
+                Some(consistent.count() as u128)
+            );
+        }
+
+        // at least 20 red, 13 green and 15 blue
+        assert_eq!(vec![rgb(20, 13, 15)], consistent_with_all(&games, &RGB, 48));
+        assert!(consistent_with_all(&games, &RGB, 47).is_empty());
+        assert_eq!(
+            consistent_with_all(&games, &RGB, 50).len(),
+            rule_outs(&games, &RGB, 50)
+                .iter()
+                .filter(|(_, ids)| ids.is_empty())
+                .count()
+        );
     }
 }
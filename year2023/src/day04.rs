@@ -1,29 +1,26 @@
-#[derive(Debug)]
-struct Card {
+use crate::helpers::scan::{lines, Scanner};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Card {
     winning: Vec<usize>,
     drawn: Vec<usize>,
 }
 
-fn parse_line(input: &str) -> Card {
-    let (w, d) = input.split_once(':').unwrap().1.split_once('|').unwrap();
-
-    return Card {
-        winning: w
-            .trim()
-            .split_ascii_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect(),
-        drawn: d
-            .trim()
-            .split_ascii_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect(),
-    };
+fn parse_line(line: &[u8]) -> Option<Card> {
+    let mut scanner = Scanner::new(line);
+    scanner.until(b':')?;
+    let winning = scanner.unsigneds().collect();
+    if !scanner.token(b"|") {
+        return None;
+    }
+    let drawn = scanner.unsigneds().collect();
+    scanner.skip_spaces();
+    return scanner.is_empty().then_some(Card { winning, drawn });
 }
 
 #[aoc_generator(day4)]
-fn parse(input: &str) -> Vec<Card> {
-    return input.lines().map(parse_line).collect();
+pub(crate) fn parse(input: &[u8]) -> Option<Vec<Card>> {
+    return lines(input).map(parse_line).collect();
 }
 
 fn part1_score(count: usize) -> usize {
@@ -84,7 +81,7 @@ mod tests {
 
     #[test]
     fn test_parser() {
-        let result = parse(EXAMPLE_1);
+        let result = parse(EXAMPLE_1.as_bytes()).unwrap();
         println!("{:?}", result);
         for card in result {
             assert_eq!(5, card.winning.len());
@@ -92,13 +89,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_malformed() {
+        assert_eq!(None, parse(b"Card 1: 41 48 | 83\nCard 2 41 | 83"));
+        assert_eq!(None, parse(b"Card 1: 41 48 83"));
+        assert_eq!(None, parse(b"Card 1: 41 48 | 83 x"));
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(13, part1(&parse(EXAMPLE_1)));
+        assert_eq!(13, part1(&parse(EXAMPLE_1.as_bytes()).unwrap()));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(30, part2(&parse(EXAMPLE_1)));
+        assert_eq!(30, part2(&parse(EXAMPLE_1.as_bytes()).unwrap()));
     }
 }
@@ -4,6 +4,7 @@ mod partial_fn {
     use std::ops::Range;
 
     use super::Int;
+    use crate::helpers::intervals::IntervalSet;
 
     #[derive(Debug, PartialEq, Clone)]
     pub struct FunctionPart {
@@ -18,6 +19,17 @@ mod partial_fn {
                 dest: dest0..(dest0 + len),
             };
         }
+
+        fn shifted(src: Range<Int>, offset: Int) -> Self {
+            return Self {
+                dest: (src.start + offset)..(src.end + offset),
+                src,
+            };
+        }
+
+        pub fn offset(&self) -> Int {
+            return self.dest.start - self.src.start;
+        }
     }
 
     #[derive(Debug, Clone)]
@@ -35,12 +47,96 @@ mod partial_fn {
                 None => input,
             };
         }
+
+        fn domain(&self) -> IntervalSet<Int> {
+            return self.parts.iter().map(|part| part.src.clone()).collect();
+        }
+
+        /// The parts with overlaps resolved the way `apply` does it, later parts winning,
+        /// sorted by source.
+        pub fn disjoint_parts(&self) -> Vec<FunctionPart> {
+            let mut covered = IntervalSet::new();
+            let mut parts = Vec::new();
+            for part in self.parts.iter().rev() {
+                let fresh = IntervalSet::from(part.src.clone()).difference(&covered);
+                for src in fresh.ranges() {
+                    parts.push(FunctionPart::shifted(src.clone(), part.offset()));
+                }
+                covered.insert(part.src.clone());
+            }
+            parts.sort_by_key(|part| part.src.start);
+            return parts;
+        }
+
+        /// `input` cut into pieces that are each moved by a single offset, sorted by source.
+        pub fn apply_range(&self, input: Range<Int>) -> Vec<FunctionPart> {
+            let input = IntervalSet::from(input);
+            let mut pieces = Vec::new();
+            for part in self.disjoint_parts() {
+                let overlap = input.intersection(&IntervalSet::from(part.src.clone()));
+                for src in overlap.ranges() {
+                    pieces.push(FunctionPart::shifted(src.clone(), part.offset()));
+                }
+            }
+            for src in input.difference(&self.domain()).ranges() {
+                pieces.push(FunctionPart::shifted(src.clone(), 0));
+            }
+            pieces.sort_by_key(|piece| piece.src.start);
+            return pieces;
+        }
+
+        /// The function applying `self` first and `next` to its result.
+        pub fn compose(&self, next: &PartialFunction) -> PartialFunction {
+            let mut parts = Vec::new();
+            for part in self.disjoint_parts() {
+                let offset = part.offset();
+                for piece in next.apply_range(part.dest.clone()) {
+                    let src = (piece.src.start - offset)..(piece.src.end - offset);
+                    parts.push(FunctionPart::shifted(src, offset + piece.offset()));
+                }
+            }
+            // where `self` is the identity only `next` moves anything
+            let domain = self.domain();
+            for part in next.disjoint_parts() {
+                let gaps = IntervalSet::from(part.src.clone()).difference(&domain);
+                for src in gaps.ranges() {
+                    parts.push(FunctionPart::shifted(src.clone(), part.offset()));
+                }
+            }
+            parts.retain(|part| part.offset() != 0);
+            parts.sort_by_key(|part| part.src.start);
+            return PartialFunction::from(parts);
+        }
+
+        /// The inverse, `None` unless the function is a bijection.
+        #[cfg(test)]
+        pub fn inverse(&self) -> Option<PartialFunction> {
+            let parts = self.disjoint_parts();
+            let images: IntervalSet<Int> = parts.iter().map(|part| part.dest.clone()).collect();
+            let moved: Int = parts.iter().map(|part| part.src.end - part.src.start).sum();
+            // the identity covers everything outside the domain, so the parts have to map
+            // their domain onto itself without overlapping
+            if images != self.domain() || images.len() != moved {
+                return None;
+            }
+            return Some(PartialFunction::from(
+                parts
+                    .into_iter()
+                    .map(|part| FunctionPart {
+                        src: part.dest,
+                        dest: part.src,
+                    })
+                    .collect(),
+            ));
+        }
     }
 }
 
 #[cfg(feature = "bruteforce")]
 use std::ops::Range;
 
+use std::fmt::Display;
+
 use partial_fn::{FunctionPart, PartialFunction};
 
 #[derive(Debug)]
@@ -95,9 +191,53 @@ fn part1(almanac: &Almanac) -> Int {
     return result.unwrap();
 }
 
+/// All stages of the almanac as a single function from seeds to locations.
+fn almanac_function(almanac: &Almanac) -> PartialFunction {
+    return almanac
+        .mappings
+        .iter()
+        .fold(PartialFunction::from(Vec::new()), |f, g| f.compose(g));
+}
+
+/// The seed with the lowest location and that location.
+fn lowest_location(almanac: &Almanac) -> (Int, Int) {
+    let almanac_fn = almanac_function(almanac);
+    return almanac
+        .seed_data
+        .chunks(2)
+        .flat_map(|x| almanac_fn.apply_range(x[0]..(x[0] + x[1])))
+        .map(|piece| (piece.src.start, piece.dest.start))
+        .min_by_key(|&(_, location)| location)
+        .unwrap();
+}
+
+#[aoc(day5, part2)]
+fn part2(almanac: &Almanac) -> Int {
+    return lowest_location(almanac).1;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Lowest {
+    seed: Int,
+    location: Int,
+}
+
+impl Display for Lowest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} (seed {})", self.location, self.seed);
+    }
+}
+
+/// Part 2 along with the seed planted there.
+#[aoc(day5, part2, seed)]
+fn part2_seed(almanac: &Almanac) -> Lowest {
+    let (seed, location) = lowest_location(almanac);
+    return Lowest { seed, location };
+}
+
 #[cfg(feature = "bruteforce")]
 #[aoc(day5, part2, bruteforce)]
-fn part2(almanac: &Almanac) -> Int {
+fn part2_bruteforce(almanac: &Almanac) -> Int {
     let seed_ranges: Vec<Range<Int>> = almanac
         .seed_data
         .chunks(2)
@@ -148,9 +288,60 @@ mod tests {
         assert_eq!(35, part1(&parse(EXAMPLE_1)));
     }
 
-    #[cfg(feature = "bruteforce")]
+    #[test]
+    fn test_apply_range() {
+        let almanac = parse(EXAMPLE_1);
+        let seed_to_soil = &almanac.mappings[0];
+        let pieces = seed_to_soil.apply_range(45..100);
+        assert_eq!(
+            vec![(45..50, 45..50), (50..98, 52..100), (98..100, 50..52)],
+            pieces
+                .into_iter()
+                .map(|piece| (piece.src, piece.dest))
+                .collect::<Vec<_>>()
+        );
+        for seed in 40..110 {
+            let pieces = seed_to_soil.apply_range(seed..(seed + 1));
+            assert_eq!(seed_to_soil.apply(seed), pieces[0].dest.start);
+        }
+    }
+
+    #[test]
+    fn test_compose_inverse() {
+        let almanac = parse(EXAMPLE_1);
+        let almanac_fn = almanac_function(&almanac);
+        let inverse = almanac_fn.inverse().unwrap();
+        for seed in -5..120 {
+            let location = almanac.mappings.iter().fold(seed, |l, b| b.apply(l));
+            assert_eq!(location, almanac_fn.apply(seed));
+            assert_eq!(seed, inverse.apply(location));
+        }
+
+        let overlapping = PartialFunction::from(vec![FunctionPart::new(0, 10, 5)]);
+        assert!(overlapping.inverse().is_none());
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(46, part2(&parse(EXAMPLE_1)));
+        assert_eq!((82, 46), lowest_location(&parse(EXAMPLE_1)));
+        let lowest = part2_seed(&parse(EXAMPLE_1));
+        assert_eq!(
+            Lowest {
+                seed: 82,
+                location: 46
+            },
+            lowest
+        );
+        assert_eq!("46 (seed 82)", lowest.to_string());
+        // the stages map ranges onto ranges one to one, so going back finds the same seed
+        let inverse = almanac_function(&parse(EXAMPLE_1)).inverse().unwrap();
+        assert_eq!(lowest.seed, inverse.apply(lowest.location));
+    }
+
+    #[cfg(feature = "bruteforce")]
+    #[test]
+    fn test_part2_bruteforce() {
+        assert_eq!(46, part2_bruteforce(&parse(EXAMPLE_1)));
     }
 }
@@ -1,45 +1,47 @@
-type Int = i64;
+use std::ops::RangeInclusive;
+
+use crate::helpers::{
+    math::{isqrt, Integer},
+    scan::{digits, lines, Scanner},
+};
 
-#[derive(Debug)]
+type Int = i64;
 
-struct Race {
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Race {
     time: Int,
     distance: Int,
 }
 
-fn parser_part1_line(line: &str) -> impl Iterator<Item = Int> + '_ {
-    return line
-        .split_once(':')
-        .unwrap()
-        .1
-        .trim()
-        .split_ascii_whitespace()
-        .map(str::parse)
-        .map(Result::unwrap);
+fn parser_part1_line(line: &[u8]) -> Option<Vec<Int>> {
+    let mut scanner = Scanner::new(line);
+    scanner.until(b':')?;
+    let values = scanner.unsigneds().collect();
+    scanner.skip_spaces();
+    return scanner.is_empty().then_some(values);
 }
 
 #[aoc_generator(day6, part1)]
-fn parser_part1(input: &str) -> Vec<Race> {
-    let (times, distances) = input.split_once('\n').unwrap();
-    return parser_part1_line(times)
-        .zip(parser_part1_line(distances))
-        .map(|(time, distance)| Race { time, distance })
-        .collect();
-}
-
-fn parser_part2_line(line: &str) -> Int {
-    line.chars().filter(char::is_ascii_digit).fold(0, |acc, c| {
-        acc.checked_mul(10).unwrap() + Int::from(c.to_digit(10).unwrap())
-    })
+pub(crate) fn parser_part1(input: &[u8]) -> Option<Vec<Race>> {
+    let mut lines = lines(input);
+    let times = parser_part1_line(lines.next()?)?;
+    let distances = parser_part1_line(lines.next()?)?;
+    return Some(
+        times
+            .into_iter()
+            .zip(distances)
+            .map(|(time, distance)| Race { time, distance })
+            .collect(),
+    );
 }
 
 #[aoc_generator(day6, part2)]
-fn parser_part2(input: &str) -> Race {
-    let (t, d) = input.split_once('\n').unwrap();
-    return Race {
-        time: parser_part2_line(t),
-        distance: parser_part2_line(d),
-    };
+pub(crate) fn parser_part2(input: &[u8]) -> Option<Race> {
+    let mut lines = lines(input);
+    return Some(Race {
+        time: digits(lines.next()?)?,
+        distance: digits(lines.next()?)?,
+    });
 }
 
 fn dist(time: Int, hold: Int) -> Int {
@@ -80,7 +82,11 @@ fn solver_part2(race: &Race) -> Int {
         }
     }
 
-    return last.unwrap() - first.unwrap() + 1;
+    // a race no hold time wins
+    return match (first, last) {
+        (Some(first), Some(last)) => last - first + 1,
+        _ => 0,
+    };
 }
 
 fn conv(int: i64) -> f64 {
@@ -93,12 +99,78 @@ fn conv(int: i64) -> f64 {
 fn solver_part2_ana(race: &Race) -> Int {
     let a: f64 = conv(race.time);
     let b: f64 = conv(race.distance);
-    let sqrt_res = b.mul_add(-4.0, a.powi(2)).sqrt();
+    let discriminant = b.mul_add(-4.0, a.powi(2));
+    // no roots, or a single one where the best hold only ties
+    if discriminant <= 0.0 {
+        return 0;
+    }
+    let sqrt_res = discriminant.sqrt();
     let f = 0.5 * (a - sqrt_res);
     let s = 0.5 * (sqrt_res + a);
     return (s.ceil() as Int) - (f.floor() as Int) - 1;
 }
 
+/// Every hold time `h` in `0..=time` with `h * (time - h) > distance`, computed exactly.
+fn winning_holds<T: Integer>(time: T, distance: T) -> Option<RangeInclusive<T>> {
+    let two = T::ONE + T::ONE;
+    // an overflowing product is certainly further than `distance`
+    let wins = |hold: T| {
+        return (time - hold).checked_mul(hold).is_none_or(|d| d > distance);
+    };
+    let middle = time / two;
+    if !wins(middle) {
+        return None;
+    }
+
+    // the first win is close to (time - sqrt(time² - 4 distance)) / 2, fix up the rounding
+    let square = time.checked_mul(time);
+    let four_distance = distance.checked_mul(two + two);
+    let mut first = match (square, four_distance) {
+        (Some(square), Some(four_distance)) if four_distance <= square => {
+            (time - isqrt(square - four_distance)) / two
+        }
+        // the roots don't fit into `T`, search the rising half instead
+        _ => {
+            let (mut low, mut high) = (T::ZERO, middle);
+            while low < high {
+                let mid = low + (high - low) / two;
+                if wins(mid) {
+                    high = mid;
+                } else {
+                    low = mid + T::ONE;
+                }
+            }
+            low
+        }
+    };
+    while !wins(first) {
+        first = first + T::ONE;
+    }
+    while first > T::ZERO && wins(first - T::ONE) {
+        first = first - T::ONE;
+    }
+    return Some(first..=time - first);
+}
+
+fn count_winning<T: Integer>(time: T, distance: T) -> T {
+    return winning_holds(time, distance).map_or(T::ZERO, |holds| {
+        return *holds.end() - *holds.start() + T::ONE;
+    });
+}
+
+#[aoc(day6, part1, exact)]
+fn solver_part1_exact(data: &[Race]) -> Int {
+    return data
+        .iter()
+        .map(|race| count_winning(race.time, race.distance))
+        .product();
+}
+
+#[aoc(day6, part2, exact)]
+fn solver_part2_exact(race: &Race) -> Int {
+    return count_winning(race.time, race.distance);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,28 +179,99 @@ mod tests {
 
     #[test]
     fn test_parser_part1() {
-        let result = parser_part1(EXAMPLE_1);
+        let result = parser_part1(EXAMPLE_1.as_bytes()).unwrap();
         println!("{:?}", result);
     }
 
     #[test]
     fn test_parser_part2() {
-        let result = parser_part2(EXAMPLE_1);
+        let result = parser_part2(EXAMPLE_1.as_bytes()).unwrap();
         println!("{:?}", result);
     }
 
+    #[test]
+    fn test_parser_values() {
+        let races = parser_part1(EXAMPLE_1.as_bytes()).unwrap();
+        assert_eq!(
+            Race {
+                time: 30,
+                distance: 200
+            },
+            races[2]
+        );
+        assert_eq!(
+            Race {
+                time: 71530,
+                distance: 940200
+            },
+            parser_part2(EXAMPLE_1.as_bytes()).unwrap()
+        );
+        assert_eq!(None, parser_part1(b"Time: 7 15\nDistance 9 40"));
+        assert_eq!(None, parser_part2(b"Time: 7 15"));
+    }
+
     #[test]
     fn test_solver_part1() {
-        assert_eq!(288, solver_part1(&parser_part1(EXAMPLE_1)));
+        assert_eq!(
+            288,
+            solver_part1(&parser_part1(EXAMPLE_1.as_bytes()).unwrap())
+        );
     }
 
     #[test]
     fn test_solver_part2() {
-        assert_eq!(71503, solver_part2(&parser_part2(EXAMPLE_1)));
+        assert_eq!(
+            71503,
+            solver_part2(&parser_part2(EXAMPLE_1.as_bytes()).unwrap())
+        );
     }
 
     #[test]
     fn test_solver_part2_ana() {
-        assert_eq!(71503, solver_part2_ana(&parser_part2(EXAMPLE_1)));
+        assert_eq!(
+            71503,
+            solver_part2_ana(&parser_part2(EXAMPLE_1.as_bytes()).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_winning_holds() {
+        assert_eq!(Some(2..=5), winning_holds(7_u64, 9));
+        // 10 * 20 == 200 is an exact root and doesn't win
+        assert_eq!(Some(11..=19), winning_holds(30_u64, 200));
+        assert_eq!(None, winning_holds(30_u64, 225));
+        assert_eq!(Some(15..=15), winning_holds(30_u64, 224));
+        assert_eq!(Some(1..=9), winning_holds(10_u32, 0));
+
+        let time = 1_u128 << 62;
+        let distance = (time / 2 - 3) * (time / 2 + 3);
+        assert_eq!(
+            Some(time / 2 - 2..=time / 2 + 2),
+            winning_holds(time, distance)
+        );
+        let time = 1_u128 << 100;
+        let first = (1 << 20) + 1;
+        assert_eq!(Some(first..=time - first), winning_holds(time, 1 << 120));
+        let time = u64::MAX;
+        assert_eq!(Some(1..=time - 1), winning_holds(time, time - 2));
+    }
+
+    #[test]
+    fn test_cross_check() {
+        for time in 1..60_i64 {
+            for distance in 0..(time * time / 4 + 2) {
+                let race = Race { time, distance };
+                let exact = solver_part2_exact(&race);
+                assert_eq!(exact, solver_part1(&[Race { time, distance }]));
+                assert_eq!(exact, solver_part2(&race));
+                assert_eq!(exact, solver_part2_ana(&race));
+            }
+        }
+        let race = parser_part2(EXAMPLE_1.as_bytes()).unwrap();
+        assert_eq!(solver_part2(&race), solver_part2_exact(&race));
+        assert_eq!(
+            288,
+            solver_part1_exact(&parser_part1(EXAMPLE_1.as_bytes()).unwrap())
+        );
     }
 }
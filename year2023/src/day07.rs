@@ -1,5 +1,8 @@
 use std::fmt::Debug;
 
+#[cfg(feature = "checked")]
+use crate::helpers::math::{checked, MathError};
+
 fn type_helper(first: u8, second: u8) -> u8 {
     return match first {
         5 => 6,
@@ -236,6 +239,35 @@ fn solver_part2(hands: &[Hand<part2::Card>]) -> i32 {
         .sum();
 }
 
+#[cfg(feature = "checked")]
+fn total_winnings_checked<T>(hands: &[Hand<T>]) -> Result<i64, MathError>
+where
+    Hand<T>: Ord + Clone,
+{
+    let mut hands: Vec<Hand<T>> = hands.to_vec();
+    hands.sort_by(Ord::cmp);
+    return hands
+        .iter()
+        .enumerate()
+        .try_fold(0_i64, |acc, (n, hand)| {
+            let rank = i64::try_from(n + 1).map_err(|_| MathError::Overflow)?;
+            let winnings = checked(rank.checked_mul(i64::from(hand.bid)))?;
+            return checked(acc.checked_add(winnings));
+        });
+}
+
+#[cfg(feature = "checked")]
+#[aoc(day7, part1, checked)]
+fn solver_part1_checked(hands: &[Hand<part1::Card>]) -> Result<i64, MathError> {
+    return total_winnings_checked(hands);
+}
+
+#[cfg(feature = "checked")]
+#[aoc(day7, part2, checked)]
+fn solver_part2_checked(hands: &[Hand<part2::Card>]) -> Result<i64, MathError> {
+    return total_winnings_checked(hands);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,4 +289,17 @@ mod tests {
     fn test_solver_part2() {
         assert_eq!(5905, solver_part2(&parser_part2(EXAMPLE_1)));
     }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_solver_checked() {
+        assert_eq!(Ok(6440), solver_part1_checked(&parser_part1(EXAMPLE_1)));
+        assert_eq!(Ok(5905), solver_part2_checked(&parser_part2(EXAMPLE_1)));
+        // 1 * 2147483647 + 2 * 2147483647 doesn't fit into the i32 of the plain solver
+        let input = "2345A 2147483647\nAAAAA 2147483647";
+        assert_eq!(
+            Ok(3 * i64::from(i32::MAX)),
+            solver_part1_checked(&parser_part1(input))
+        );
+    }
 }
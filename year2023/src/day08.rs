@@ -1,7 +1,6 @@
-use std::{
-    collections::HashMap,
-    ops::{Div, Mul, Rem},
-};
+use std::collections::HashMap;
+
+use crate::helpers::math::{checked, lcm, MathError};
 
 pub struct Network {
     inner: HashMap<u16, (u16, u16)>,
@@ -56,23 +55,6 @@ impl From<char> for Instruction {
     }
 }
 
-fn gcd<T: Copy + Eq + Ord + Rem<Output = T>>(zero: T, mut a: T, mut b: T) -> T {
-    while b != zero {
-        let t = b;
-        b = a.rem(b);
-        a = t;
-    }
-    return a;
-}
-
-fn lcm<T: Copy + Eq + Ord + Mul<Output = T> + Div<Output = T> + Rem<Output = T>>(
-    zero: T,
-    a: T,
-    b: T,
-) -> T {
-    return a.mul(b.div(gcd(zero, a, b)));
-}
-
 #[aoc_generator(day8)]
 pub fn parser(input: &str) -> (Vec<Instruction>, Network) {
     let parts = input.split_once("\n\n").unwrap();
@@ -89,10 +71,10 @@ pub fn parser(input: &str) -> (Vec<Instruction>, Network) {
     return (instructions, network);
 }
 
-#[aoc(day8, part1)]
-pub fn solver_part1((instructions, network): &(Vec<Instruction>, Network)) -> i32 {
+/// The number of steps from `AAA` to `ZZZ`.
+fn steps_to_zzz((instructions, network): &(Vec<Instruction>, Network)) -> Result<u64, MathError> {
     let mut instructions = instructions.iter().cycle();
-    let mut steps = 0;
+    let mut steps: u64 = 0;
     let mut current_key: u16 = Network::str_to_u16("AAA");
 
     while current_key != Network::str_to_u16("ZZZ") {
@@ -102,15 +84,25 @@ pub fn solver_part1((instructions, network): &(Vec<Instruction>, Network)) -> i3
             Instruction::Left => *left,
             Instruction::Right => *right,
         };
-        steps += 1;
+        steps = checked(steps.checked_add(1))?;
     }
 
-    return steps;
+    return Ok(steps);
 }
 
-#[aoc(day8, part2)]
-pub fn solver_part2((instr, network): &(Vec<Instruction>, Network)) -> u64 {
-    let mut instructions = instr.iter().cycle();
+#[aoc(day8, part1)]
+pub fn solver_part1(data: &(Vec<Instruction>, Network)) -> i32 {
+    return i32::try_from(steps_to_zzz(data).unwrap()).unwrap();
+}
+
+#[cfg(feature = "checked")]
+#[aoc(day8, part1, checked)]
+pub fn solver_part1_checked(data: &(Vec<Instruction>, Network)) -> Result<u64, MathError> {
+    return steps_to_zzz(data);
+}
+
+/// The steps every ghost takes from its `..A` node to the first `..Z` node.
+fn cycle_lengths((instr, network): &(Vec<Instruction>, Network)) -> Result<Vec<u64>, MathError> {
     let nodes: Vec<u16> = network
         .inner
         .keys()
@@ -120,8 +112,10 @@ pub fn solver_part2((instr, network): &(Vec<Instruction>, Network)) -> u64 {
     let mut cycles: Vec<u64> = vec![];
 
     for node in nodes {
+        // every ghost starts at the first instruction
+        let mut instructions = instr.iter().cycle();
         let mut current_key = node;
-        let mut cycle = 0;
+        let mut cycle: u64 = 0;
 
         while current_key % 26 != 25 {
             let instruction = instructions.next().unwrap();
@@ -130,12 +124,27 @@ pub fn solver_part2((instr, network): &(Vec<Instruction>, Network)) -> u64 {
                 Instruction::Left => *left,
                 Instruction::Right => *right,
             };
-            cycle += 1;
+            cycle = checked(cycle.checked_add(1))?;
         }
         cycles.push(cycle);
     }
 
-    return cycles.iter().copied().reduce(|a, b| lcm(0, a, b)).unwrap();
+    return Ok(cycles);
+}
+
+#[aoc(day8, part2)]
+pub fn solver_part2(data: &(Vec<Instruction>, Network)) -> u64 {
+    return cycle_lengths(data)
+        .unwrap()
+        .iter()
+        .try_fold(1, |a, &b| lcm(a, b))
+        .unwrap();
+}
+
+#[cfg(feature = "checked")]
+#[aoc(day8, part2, checked)]
+pub fn solver_part2_checked(data: &(Vec<Instruction>, Network)) -> Result<u64, MathError> {
+    return cycle_lengths(data)?.iter().try_fold(1, |a, &b| lcm(a, b));
 }
 
 #[cfg(test)]
@@ -152,9 +161,34 @@ mod tests {
         assert_eq!(6, solver_part1(&parser(EXAMPLE_2)));
     }
 
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_part1_checked() {
+        assert_eq!(Ok(2), solver_part1_checked(&parser(EXAMPLE_1)));
+        assert_eq!(Ok(6), solver_part1_checked(&parser(EXAMPLE_2)));
+    }
+
+    #[test]
+    fn test_cycle_lengths() {
+        let example = EXAMPLE_3.replace("1", "F").replace("2", "H");
+        // the start nodes come out of a HashMap in a different order each time
+        for _ in 0..20 {
+            let mut cycles = cycle_lengths(&parser(&example)).unwrap();
+            cycles.sort();
+            assert_eq!(vec![2, 3], cycles);
+        }
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_part2_checked() {
+        let example = EXAMPLE_3.replace("1", "F").replace("2", "H");
+        assert_eq!(Ok(6), solver_part2_checked(&parser(&example)));
+    }
+
     #[test]
     fn test_lcm() {
-        assert_eq!(805261, lcm(0_u64, 18727, 13201));
+        assert_eq!(805261, lcm(18727_u64, 13201).unwrap());
     }
 
     #[test]
@@ -1,47 +1,151 @@
+use std::{error::Error, fmt::Display};
+
+use crate::helpers::scan::{lines, Scanner};
+
 type Int = i64;
 
+fn parse_line(line: &[u8]) -> Option<Vec<Int>> {
+    let mut scanner = Scanner::new(line);
+    let values = scanner.signeds().collect();
+    scanner.skip_spaces();
+    return scanner.is_empty().then_some(values);
+}
+
 #[aoc_generator(day9)]
-fn parse(input: &str) -> Vec<Vec<Int>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(str::parse)
-                .map(Result::unwrap)
-                .collect::<Vec<Int>>()
-        })
-        .collect()
-}
-
-fn extrapolate_forwards(v: &[Int]) -> Int {
-    let mut last: Vec<Int> = Vec::new();
-    let mut temp = v.to_owned();
-    while temp.iter().any(|&x| x != 0) {
-        last.push(*temp.last().unwrap());
-        temp = temp.windows(2).map(|a| a[1] - a[0]).collect::<Vec<Int>>();
+pub(crate) fn parse(input: &[u8]) -> Option<Vec<Vec<Int>>> {
+    return lines(input).map(parse_line).collect();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolynomialError {
+    Empty,
+    /// The differences never reduce to all zeros.
+    NotPolynomial,
+    Overflow,
+}
+
+impl Display for PolynomialError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Self::Empty => "empty sequence",
+            Self::NotPolynomial => "the differences never reduce to zero",
+            Self::Overflow => "arithmetic overflow",
+        };
+        return write!(f, "{}", message);
     }
-    last.reverse();
-    return last.iter().copied().reduce(|acc, x| acc + x).unwrap();
 }
 
-fn extrapolate_backwards(v: &[Int]) -> Int {
-    let mut first: Vec<Int> = Vec::new();
-    let mut temp = v.to_owned();
-    while temp.iter().any(|&x| x != 0) {
-        first.push(*temp.first().unwrap());
-        temp = temp.windows(2).map(|a| a[1] - a[0]).collect::<Vec<Int>>();
+impl Error for PolynomialError {}
+
+/// A sequence as a polynomial in Newton's forward form, `y(x) = Σ Δᵏy₀ · C(x, k)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Polynomial {
+    /// The leading entry of every non-zero row of the difference table.
+    differences: Vec<i128>,
+}
+
+impl Polynomial {
+    fn fit(sequence: &[Int]) -> Result<Self, PolynomialError> {
+        if sequence.is_empty() {
+            return Err(PolynomialError::Empty);
+        }
+        // the difference table is built in place, row k occupying the first len - k entries
+        let mut row: Vec<i128> = sequence.iter().map(|&x| i128::from(x)).collect();
+        let mut differences = Vec::new();
+        for len in (1..=row.len()).rev() {
+            if row[..len].iter().all(|&x| x == 0) {
+                return Ok(Self { differences });
+            }
+            differences.push(row[0]);
+            for i in 0..len - 1 {
+                row[i] = row[i + 1]
+                    .checked_sub(row[i])
+                    .ok_or(PolynomialError::Overflow)?;
+            }
+        }
+        return Err(PolynomialError::NotPolynomial);
     }
-    first.reverse();
-    return first.iter().copied().reduce(|acc, x| x - acc).unwrap();
+
+    /// The degree, where the zero polynomial counts as degree 0.
+    fn degree(&self) -> usize {
+        return self.differences.len().saturating_sub(1);
+    }
+
+    /// The value at `index`, the sequence itself starting at index 0.
+    fn evaluate(&self, index: i128) -> Result<i128, PolynomialError> {
+        let mut result: i128 = 0;
+        // C(index, k), which stays an integer for negative indices too
+        let mut binomial: i128 = 1;
+        for (k, &difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                let k = k as i128;
+                binomial = binomial
+                    .checked_mul(index - k + 1)
+                    .ok_or(PolynomialError::Overflow)?
+                    / k;
+            }
+            let term = difference
+                .checked_mul(binomial)
+                .ok_or(PolynomialError::Overflow)?;
+            result = result.checked_add(term).ok_or(PolynomialError::Overflow)?;
+        }
+        return Ok(result);
+    }
+}
+
+fn extrapolate(v: &[Int], index: i128) -> Result<Int, PolynomialError> {
+    return value_at(&Polynomial::fit(v)?, index);
+}
+
+fn value_at(polynomial: &Polynomial, index: i128) -> Result<Int, PolynomialError> {
+    let value = polynomial.evaluate(index)?;
+    return value.try_into().map_err(|_| PolynomialError::Overflow);
+}
+
+fn extrapolate_forwards(v: &[Int]) -> Result<Int, PolynomialError> {
+    return extrapolate(v, v.len() as i128);
+}
+
+fn extrapolate_backwards(v: &[Int]) -> Result<Int, PolynomialError> {
+    return extrapolate(v, -1);
 }
 
 #[aoc(day9, part1)]
-pub fn solve_part1(data: &[Vec<Int>]) -> Int {
+pub fn solve_part1(data: &[Vec<Int>]) -> Result<Int, PolynomialError> {
     return data.iter().map(|x| extrapolate_forwards(x)).sum();
 }
 
+#[derive(Debug, PartialEq, Eq)]
+struct Prediction {
+    sum: Int,
+    /// The highest degree among the sequences.
+    degree: usize,
+}
+
+impl Display for Prediction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} (degree up to {})", self.sum, self.degree);
+    }
+}
+
+/// Part 1 along with the degree of the polynomials behind it.
+#[aoc(day9, part1, degree)]
+fn solve_part1_degree(data: &[Vec<Int>]) -> Result<Prediction, PolynomialError> {
+    let mut prediction = Prediction { sum: 0, degree: 0 };
+    for sequence in data {
+        let polynomial = Polynomial::fit(sequence)?;
+        let value = value_at(&polynomial, sequence.len() as i128)?;
+        prediction.sum = prediction
+            .sum
+            .checked_add(value)
+            .ok_or(PolynomialError::Overflow)?;
+        prediction.degree = prediction.degree.max(polynomial.degree());
+    }
+    return Ok(prediction);
+}
+
 #[aoc(day9, part2)]
-fn solve_part2(data: &[Vec<Int>]) -> Int {
+fn solve_part2(data: &[Vec<Int>]) -> Result<Int, PolynomialError> {
     return data.iter().map(|x| extrapolate_backwards(x)).sum();
 }
 
@@ -53,25 +157,89 @@ mod tests {
 
     #[test]
     fn test_extrapolate_forwards() {
-        assert_eq!(18, extrapolate_forwards(&vec![0, 3, 6, 9, 12, 15]));
-        assert_eq!(28, extrapolate_forwards(&vec![1, 3, 6, 10, 15, 21]));
-        assert_eq!(68, extrapolate_forwards(&vec![10, 13, 16, 21, 30, 45]));
+        assert_eq!(Ok(18), extrapolate_forwards(&[0, 3, 6, 9, 12, 15]));
+        assert_eq!(Ok(28), extrapolate_forwards(&[1, 3, 6, 10, 15, 21]));
+        assert_eq!(Ok(68), extrapolate_forwards(&[10, 13, 16, 21, 30, 45]));
     }
 
     #[test]
     fn test_extrapolate_backwards() {
-        assert_eq!(-3, extrapolate_backwards(&vec![0, 3, 6, 9, 12, 15]));
-        assert_eq!(0, extrapolate_backwards(&vec![1, 3, 6, 10, 15, 21]));
-        assert_eq!(5, extrapolate_backwards(&vec![10, 13, 16, 21, 30, 45]));
+        assert_eq!(Ok(-3), extrapolate_backwards(&[0, 3, 6, 9, 12, 15]));
+        assert_eq!(Ok(0), extrapolate_backwards(&[1, 3, 6, 10, 15, 21]));
+        assert_eq!(Ok(5), extrapolate_backwards(&[10, 13, 16, 21, 30, 45]));
+    }
+
+    #[test]
+    fn test_polynomial() {
+        let squares = Polynomial::fit(&[1, 4, 9, 16]).unwrap();
+        assert_eq!(2, squares.degree());
+        assert_eq!(Ok(1), squares.evaluate(-2));
+        assert_eq!(
+            Ok(1_000_000_000_000_000_000_000_000),
+            squares.evaluate(999_999_999_999)
+        );
+        assert_eq!(
+            Err(PolynomialError::Overflow),
+            squares.evaluate(i128::MAX / 2)
+        );
+
+        let cubes = Polynomial::fit(&[0, 1, 8, 27, 64, 125]).unwrap();
+        assert_eq!(3, cubes.degree());
+        assert_eq!(Ok(-1_000_000_000_000_000_000), cubes.evaluate(-1_000_000));
+
+        assert_eq!(0, Polynomial::fit(&[0, 0]).unwrap().degree());
+        assert_eq!(Ok(7), Polynomial::fit(&[7, 7]).unwrap().evaluate(100));
+        assert_eq!(Err(PolynomialError::NotPolynomial), Polynomial::fit(&[7]));
+        assert_eq!(Err(PolynomialError::Empty), Polynomial::fit(&[]));
+        assert_eq!(
+            Err(PolynomialError::NotPolynomial),
+            Polynomial::fit(&[1, 2, 4, 8])
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Some(vec![vec![-4, 0, -12]]), parse(b"-4 0 -12\n"));
+        assert_eq!(None, parse(b"1 2\n3 - 4"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(PolynomialError::NotPolynomial),
+            solve_part1(&[vec![1, 2, 4]])
+        );
+        assert_eq!(Err(PolynomialError::Empty), solve_part2(&[vec![]]));
+        assert_eq!(
+            Err(PolynomialError::Overflow),
+            extrapolate_forwards(&[Int::MAX - 2, Int::MAX - 1, Int::MAX])
+        );
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(114, solve_part1(&parse(EXAMPLE_1)));
+        assert_eq!(Ok(114), solve_part1(&parse(EXAMPLE_1.as_bytes()).unwrap()));
+    }
+
+    #[test]
+    fn test_part1_degree() {
+        let prediction = solve_part1_degree(&parse(EXAMPLE_1.as_bytes()).unwrap());
+        assert_eq!(
+            Ok(Prediction {
+                sum: 114,
+                degree: 3
+            }),
+            prediction
+        );
+        assert_eq!("114 (degree up to 3)", prediction.unwrap().to_string());
+        assert_eq!(
+            Err(PolynomialError::Overflow),
+            solve_part1_degree(&[vec![i64::MAX; 2], vec![1, 1]])
+        );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(2, solve_part2(&parse(EXAMPLE_1)));
+        assert_eq!(Ok(2), solve_part2(&parse(EXAMPLE_1.as_bytes()).unwrap()));
     }
 }
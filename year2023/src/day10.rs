@@ -1,17 +1,12 @@
 use std::fmt::{Debug, Write};
 
-use crate::helpers::graph::{NodeIndex, UndirectedGraph};
+use crate::helpers::graph::{
+    grid::{grid_graph, Direction, GridMap},
+    NodeIndex, UndirectedGraph,
+};
 
 type Grid = UndirectedGraph<Node, ()>;
 
-#[derive(Debug)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
 use Direction::*;
 
 #[derive(Clone, PartialEq)]
@@ -73,88 +68,41 @@ impl Node {
     }
 }
 
-fn add_connection(
-    graph: &mut Grid,
-    a: NodeIndex,
-    dir_a: Direction,
-    b: NodeIndex,
-    dir_b: Direction,
-) {
-    let an = graph.get_node(a).unwrap();
-    let bn = graph.get_node(b).unwrap();
-
-    assert_ne!(a, b);
-
-    if an.has_connection(dir_a) && bn.has_connection(dir_b) {
-        graph.add_edge(b, a, ()).unwrap();
-    }
-}
-
 #[aoc_generator(day10)]
-fn parse(input: &str) -> (Grid, NodeIndex) {
-    let mut graph = UndirectedGraph::new();
-
-    let mut start: Option<NodeIndex> = None;
-
-    let mut prev: Vec<NodeIndex> = Vec::new();
-    let mut current: Vec<NodeIndex> = Vec::new();
-
-    for line in input.lines() {
-        for (i, c) in line.chars().enumerate() {
-            let data = Node::from(c);
-            let node = graph.add_node(data.clone());
-            current.push(node);
-
-            if data == Node::Start {
-                start = Some(node);
-            }
-
-            // connect left
-            if i > 0 {
-                let left = current.get(i.checked_sub(1).unwrap());
-                add_connection(&mut graph, node, West, *left.unwrap(), East)
-            }
-
-            // conect top
-            let top = prev.get(i);
-            if top.is_some() {
-                add_connection(&mut graph, node, North, *top.unwrap(), South)
-            }
-        }
-        prev = current;
-        current = Vec::new();
-    }
-
-    return (graph, start.unwrap());
+fn parse(input: &str) -> (Grid, NodeIndex, GridMap) {
+    let (graph, cells) = grid_graph(
+        input.lines().map(|line| line.chars().map(Node::from)),
+        |node| *node != Node::None,
+        |a, dir, b| a.has_connection(dir) && b.has_connection(dir.opposite()),
+    );
+    let start = (0..graph.node_count())
+        .find(|&node| *graph.get_node(node).unwrap() == Node::Start)
+        .unwrap();
+    return (graph, start, cells);
 }
 
 #[aoc(day10, part1)]
-fn solve_part1((grid, start): &(Grid, NodeIndex)) -> usize {
-    let mut indices: Vec<NodeIndex> = Vec::new();
-    let mut current = *start;
-
-    loop {
-        let neighbors: Vec<NodeIndex> = grid
-            .neighbors(current)
-            .unwrap()
-            .filter(|n| !indices.contains(n))
-            .collect();
-        match neighbors.first() {
-            Some(n) => current = *n,
-            None => {
-                break;
-            }
-        };
-
-        indices.push(current);
-    }
-    return indices.len() / 2;
+fn solve_part1((grid, start, _): &(Grid, NodeIndex, GridMap)) -> usize {
+    return grid.cycle_through(*start).unwrap().len() / 2;
 }
 
 #[aoc(day10, part2)]
-fn solve_part2((grid, start): &(Grid, NodeIndex)) -> usize {
-
-    return 0;
+fn solve_part2((grid, start, cells): &(Grid, NodeIndex, GridMap)) -> usize {
+    let pipe_loop = grid.cycle_through(*start).unwrap();
+    let vertices: Vec<(i64, i64)> = pipe_loop
+        .iter()
+        .map(|&n| cells.cell(n).unwrap())
+        .map(|(row, col)| (row as i64, col as i64))
+        .collect();
+
+    // shoelace formula for the area, then pick's theorem for the enclosed tiles
+    let double_area = vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum::<i64>()
+        .unsigned_abs() as usize;
+    return (double_area - pipe_loop.len()) / 2 + 1;
 }
 
 #[cfg(test)]
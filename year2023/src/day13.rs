@@ -1,3 +1,6 @@
+#[cfg(feature = "checked")]
+use crate::helpers::math::{checked, MathError};
+
 fn parse_line(input: &str) -> Vec<u32> {
     input.chars().map(|c| match c {
         '.' => 0_u32,
@@ -60,6 +63,30 @@ pub fn solver_part2(input: &[(Vec<u32>, Vec<u32>)]) -> u32 {
     return input.iter().map(|(rows, cols)| 100 * solve_pattern_smudge(rows) + solve_pattern_smudge(cols)).sum()
 }
 
+#[cfg(feature = "checked")]
+fn summarize_checked(
+    input: &[(Vec<u32>, Vec<u32>)],
+    solve: fn(&Vec<u32>) -> u32,
+) -> Result<u64, MathError> {
+    return input.iter().try_fold(0_u64, |acc, (rows, cols)| {
+        let rows = checked(100_u64.checked_mul(u64::from(solve(rows))))?;
+        let note = checked(rows.checked_add(u64::from(solve(cols))))?;
+        return checked(acc.checked_add(note));
+    });
+}
+
+#[cfg(feature = "checked")]
+#[aoc(day13, part1, checked)]
+pub fn solver_part1_checked(input: &[(Vec<u32>, Vec<u32>)]) -> Result<u64, MathError> {
+    return summarize_checked(input, solve_pattern_reflection);
+}
+
+#[cfg(feature = "checked")]
+#[aoc(day13, part2, checked)]
+pub fn solver_part2_checked(input: &[(Vec<u32>, Vec<u32>)]) -> Result<u64, MathError> {
+    return summarize_checked(input, solve_pattern_smudge);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,4 +101,12 @@ mod tests {
     fn test_solver_part2() {
         assert_eq!(300, solver_part2(&vec![parse_block(EXAMPLE_BLOCK_1)]));
     }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_solver_checked() {
+        let input = vec![parse_block(EXAMPLE_BLOCK_1)];
+        assert_eq!(Ok(5), solver_part1_checked(&input));
+        assert_eq!(Ok(300), solver_part2_checked(&input));
+    }
 }
@@ -1,4 +1,9 @@
-#[derive(PartialEq)]
+#[cfg(feature = "checked")]
+use crate::helpers::math::{checked, MathError};
+
+use crate::helpers::cycle::state_after;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum PlatformTile {
     StoneRound,
     StoneCube,
@@ -21,8 +26,7 @@ pub fn parser(input: &str) -> Vec<Vec<PlatformTile>> {
 
 /* Solvers */
 
-#[aoc(day14, part1)]
-pub fn solver_part1(input: &[Vec<PlatformTile>]) -> u32 {
+fn tilt_north(input: &[Vec<PlatformTile>]) -> Vec<Vec<&PlatformTile>> {
     let mut input = Vec::from_iter(input.iter().map(|x| Vec::from_iter(x.iter())));
     let l = input.len();
     for _ in 0..l {
@@ -39,9 +43,95 @@ pub fn solver_part1(input: &[Vec<PlatformTile>]) -> u32 {
             }
         }
     }
+    return input;
+}
+
+#[aoc(day14, part1)]
+pub fn solver_part1(input: &[Vec<PlatformTile>]) -> u32 {
+    let input = tilt_north(input);
+    let l = input.len();
     return input.iter().enumerate().map(|(i, x)| (l-i) as u32 * x.iter().filter(|y| *y == &&PlatformTile::StoneRound).count() as u32).sum::<u32>();
 }
 
+#[cfg(feature = "checked")]
+#[aoc(day14, part1, checked)]
+fn solver_part1_checked(input: &[Vec<PlatformTile>]) -> Result<u64, MathError> {
+    let input = tilt_north(input);
+    let l = input.len();
+    return input.iter().enumerate().try_fold(0_u64, |acc, (i, x)| {
+        let rocks = x.iter().filter(|y| *y == &&PlatformTile::StoneRound).count();
+        let load = checked(((l - i) as u64).checked_mul(rocks as u64))?;
+        return checked(acc.checked_add(load));
+    });
+}
+
+fn roll_north(grid: &mut [Vec<PlatformTile>]) {
+    for col in 0..grid[0].len() {
+        let mut free = 0;
+        for row in 0..grid.len() {
+            match grid[row][col] {
+                PlatformTile::StoneCube => free = row + 1,
+                PlatformTile::StoneRound => {
+                    grid[row][col] = PlatformTile::Empty;
+                    grid[free][col] = PlatformTile::StoneRound;
+                    free += 1;
+                }
+                PlatformTile::Empty => {}
+            }
+        }
+    }
+}
+
+fn rotate_clockwise(grid: &[Vec<PlatformTile>]) -> Vec<Vec<PlatformTile>> {
+    return (0..grid[0].len())
+        .map(|col| (0..grid.len()).rev().map(|row| grid[row][col]).collect())
+        .collect();
+}
+
+// north, west, south and east, by rolling north and turning the platform each time
+fn spin_cycle(grid: &[Vec<PlatformTile>]) -> Vec<Vec<PlatformTile>> {
+    let mut grid = grid.to_vec();
+    for _ in 0..4 {
+        roll_north(&mut grid);
+        grid = rotate_clockwise(&grid);
+    }
+    return grid;
+}
+
+fn north_load(grid: &[Vec<PlatformTile>]) -> usize {
+    let l = grid.len();
+    return grid
+        .iter()
+        .enumerate()
+        .map(|(i, row)| (l - i) * row.iter().filter(|&&tile| tile == PlatformTile::StoneRound).count())
+        .sum();
+}
+
+#[cfg(feature = "checked")]
+fn north_load_checked(grid: &[Vec<PlatformTile>]) -> Result<u64, MathError> {
+    let l = grid.len();
+    return grid.iter().enumerate().try_fold(0_u64, |acc, (i, row)| {
+        let rocks = row.iter().filter(|&&tile| tile == PlatformTile::StoneRound).count();
+        let load = checked(((l - i) as u64).checked_mul(rocks as u64))?;
+        return checked(acc.checked_add(load));
+    });
+}
+
+fn spun(input: &[Vec<PlatformTile>]) -> Vec<Vec<PlatformTile>> {
+    return state_after(input.to_vec(), |grid| spin_cycle(grid), 1_000_000_000);
+}
+
+#[aoc(day14, part2)]
+fn solver_part2(input: &[Vec<PlatformTile>]) -> usize {
+    return north_load(&spun(input));
+}
+
+#[cfg(feature = "checked")]
+#[aoc(day14, part2, checked)]
+fn solver_part2_checked(input: &[Vec<PlatformTile>]) -> Result<u64, MathError> {
+    return north_load_checked(&spun(input));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,5 +141,22 @@ mod tests {
     fn test_solver_part1() {
         assert_eq!(136, solver_part1(&parser(EXAMPLE_1)));
     }
+
+    #[test]
+    fn test_solver_part2() {
+        assert_eq!(64, solver_part2(&parser(EXAMPLE_1)));
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_solver_part1_checked() {
+        assert_eq!(Ok(136), solver_part1_checked(&parser(EXAMPLE_1)));
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_solver_part2_checked() {
+        assert_eq!(Ok(64), solver_part2_checked(&parser(EXAMPLE_1)));
+    }
 }
 
@@ -0,0 +1,154 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Floyd's tortoise and hare on the sequence `start, step(start), ...`.
+/// Returns `(prefix, period)`, the states from index `prefix` on repeating every `period` steps.
+pub fn floyd<T: PartialEq, F: Fn(&T) -> T>(start: T, step: F) -> (usize, usize) {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    // the distance from the start to the meeting point is a multiple of the period
+    let mut prefix = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    return (prefix, period);
+}
+
+/// Brent's algorithm, usually needing fewer calls to `step` than `floyd`.
+/// Returns `(prefix, period)`.
+pub fn brent<T: PartialEq + Clone, F: Fn(&T) -> T>(start: T, step: F) -> (usize, usize) {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // walk two pointers `period` apart until they meet at the start of the cycle
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    return (prefix, period);
+}
+
+/// Remembers every state it has seen, for states that are cheap to hash but expensive to
+/// step. Returns `(prefix, period)` and all states up to the first repetition.
+pub fn find_cycle<T: Hash + Eq + Clone, F: FnMut(&T) -> T>(
+    start: T,
+    mut step: F,
+) -> ((usize, usize), Vec<T>) {
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    let mut history = vec![start];
+    loop {
+        let current = &history[history.len() - 1];
+        if let Some(&first) = seen.get(current) {
+            history.pop();
+            return ((first, history.len() - first), history);
+        }
+        seen.insert(current.clone(), history.len() - 1);
+        let next = step(current);
+        history.push(next);
+    }
+}
+
+/// The index within `0..prefix + period` holding the same state as index `n`.
+pub fn equivalent_index((prefix, period): (usize, usize), n: u64) -> usize {
+    if n < prefix as u64 {
+        return n as usize;
+    }
+    return prefix + ((n - prefix as u64) % period as u64) as usize;
+}
+
+/// The state after `n` applications of `step`, stopping as soon as the states repeat.
+pub fn state_after<T: Hash + Eq + Clone, F: FnMut(&T) -> T>(start: T, mut step: F, n: u64) -> T {
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    let mut history = vec![start];
+    loop {
+        let index = history.len() - 1;
+        if index as u64 == n {
+            return history.pop().unwrap();
+        }
+        let current = &history[index];
+        if let Some(&first) = seen.get(current) {
+            let target = equivalent_index((first, index - first), n);
+            return history.swap_remove(target);
+        }
+        seen.insert(current.clone(), index);
+        let next = step(current);
+        history.push(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 7 → 22 → ... → 4 → 2 → 1 → 4 → 2 → 1, the tail of every collatz sequence
+    fn collatz(&n: &u64) -> u64 {
+        if n % 2 == 0 {
+            return n / 2;
+        }
+        return 3 * n + 1;
+    }
+
+    #[test]
+    fn test_floyd_brent() {
+        assert_eq!((14, 3), floyd(7, collatz));
+        assert_eq!((14, 3), brent(7, collatz));
+        assert_eq!((0, 3), floyd(4, collatz));
+        assert_eq!((0, 3), brent(1, collatz));
+        assert_eq!((0, 1), brent(5_u8, |&x| x));
+
+        let lcg = |&x: &u32| (x * 5 + 3) % 1000;
+        assert_eq!(floyd(17, lcg), brent(17, lcg));
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let ((prefix, period), history) = find_cycle(7, collatz);
+        assert_eq!((14, 3), (prefix, period));
+        assert_eq!(17, history.len());
+        assert_eq!(&[4, 2, 1], &history[14..]);
+    }
+
+    #[test]
+    fn test_state_after() {
+        assert_eq!(7, state_after(7, collatz, 0));
+        assert_eq!(22, state_after(7, collatz, 1));
+        assert_eq!(4, state_after(7, collatz, 14));
+        // 1_000_000_000 - 14 leaves a remainder of 2 modulo 3
+        assert_eq!(2, state_after(7, collatz, 1_000_000_000 - 1));
+        assert_eq!(1, state_after(7, collatz, 1_000_000_000));
+        assert_eq!(4, state_after(7, collatz, 1_000_000_000 + 1));
+        assert_eq!(16, equivalent_index((14, 3), 1_000_000_000));
+    }
+}
@@ -0,0 +1,434 @@
+use std::ops::{Add, Sub};
+
+use super::rational::Ratio;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    pub x: i128,
+    pub y: i128,
+}
+
+impl Point {
+    pub fn new(x: i128, y: i128) -> Self {
+        return Self { x, y };
+    }
+
+    pub fn cross(self, other: Self) -> i128 {
+        return self.x * other.y - self.y * other.x;
+    }
+
+    pub fn dot(self, other: Self) -> i128 {
+        return self.x * other.x + self.y * other.y;
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        return Self::new(self.x + rhs.x, self.y + rhs.y);
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        return Self::new(self.x - rhs.x, self.y - rhs.y);
+    }
+}
+
+/// A point with exact rational coordinates, as intersections usually aren't on the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RationalPoint {
+    pub x: Ratio,
+    pub y: Ratio,
+}
+
+impl RationalPoint {
+    /// Whether the point lies in the square `min..=max` on both axes.
+    pub fn within(&self, min: i128, max: i128) -> bool {
+        let (min, max) = (Ratio::from(min), Ratio::from(max));
+        return (min..=max).contains(&self.x) && (min..=max).contains(&self.y);
+    }
+}
+
+impl From<Point> for RationalPoint {
+    fn from(point: Point) -> Self {
+        return Self {
+            x: Ratio::from(point.x),
+            y: Ratio::from(point.y),
+        };
+    }
+}
+
+/// Allowed values of the parameter `t` in `origin + t * direction`, `None` is unbounded.
+type Bounds = (Option<Ratio>, Option<Ratio>);
+
+/// Anything of the form `origin + t * direction` with `t` restricted to some interval.
+pub trait Linear {
+    fn origin(&self) -> Point;
+    fn direction(&self) -> Point;
+    fn bounds(&self) -> Bounds;
+
+    fn at(&self, t: Ratio) -> RationalPoint {
+        let (origin, direction) = (self.origin(), self.direction());
+        return RationalPoint {
+            x: Ratio::from(origin.x) + t * Ratio::from(direction.x),
+            y: Ratio::from(origin.y) + t * Ratio::from(direction.y),
+        };
+    }
+}
+
+fn non_zero(direction: Point) -> Point {
+    assert_ne!(Point::default(), direction, "a direction can't be zero");
+    return direction;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line {
+    origin: Point,
+    direction: Point,
+}
+
+impl Line {
+    pub fn new(origin: Point, direction: Point) -> Self {
+        return Self {
+            origin,
+            direction: non_zero(direction),
+        };
+    }
+}
+
+impl Linear for Line {
+    fn origin(&self) -> Point {
+        return self.origin;
+    }
+
+    fn direction(&self) -> Point {
+        return self.direction;
+    }
+
+    fn bounds(&self) -> Bounds {
+        return (None, None);
+    }
+}
+
+/// Starts at `origin` and goes on forever, like a hailstone from now on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ray {
+    origin: Point,
+    direction: Point,
+}
+
+impl Ray {
+    pub fn new(origin: Point, direction: Point) -> Self {
+        return Self {
+            origin,
+            direction: non_zero(direction),
+        };
+    }
+
+    /// Where the future paths of both rays meet inside the square `min..=max`, if they do.
+    pub fn future_intersection_within(
+        &self,
+        other: &Ray,
+        min: i128,
+        max: i128,
+    ) -> Option<Intersection> {
+        let intersection = intersect(self, other);
+        let touches = match intersection {
+            Intersection::None => false,
+            Intersection::Point(point) => point.within(min, max),
+            Intersection::Overlap(from, to) => {
+                let t = |p: Option<RationalPoint>| p.map(|p| self.parameter_of(&p));
+                clip(self, (t(from), t(to)), min, max)
+            }
+        };
+        return touches.then_some(intersection);
+    }
+
+    // the inverse of `at` for points on the ray
+    fn parameter_of(&self, point: &RationalPoint) -> Ratio {
+        if self.direction.x != 0 {
+            return (point.x - Ratio::from(self.origin.x)) / Ratio::from(self.direction.x);
+        }
+        return (point.y - Ratio::from(self.origin.y)) / Ratio::from(self.direction.y);
+    }
+}
+
+impl Linear for Ray {
+    fn origin(&self) -> Point {
+        return self.origin;
+    }
+
+    fn direction(&self) -> Point {
+        return self.direction;
+    }
+
+    fn bounds(&self) -> Bounds {
+        return (Some(Ratio::ZERO), None);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    start: Point,
+    end: Point,
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Self {
+        non_zero(end - start);
+        return Self { start, end };
+    }
+}
+
+impl Linear for Segment {
+    fn origin(&self) -> Point {
+        return self.start;
+    }
+
+    fn direction(&self) -> Point {
+        return self.end - self.start;
+    }
+
+    fn bounds(&self) -> Bounds {
+        return (Some(Ratio::ZERO), Some(Ratio::ONE));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intersection {
+    None,
+    Point(RationalPoint),
+    /// Collinear with a shared stretch, given from one end to the other along the first
+    /// argument, `None` where it is unbounded.
+    Overlap(Option<RationalPoint>, Option<RationalPoint>),
+}
+
+pub fn parallel<A: Linear, B: Linear>(a: &A, b: &B) -> bool {
+    return a.direction().cross(b.direction()) == 0;
+}
+
+pub fn collinear<A: Linear, B: Linear>(a: &A, b: &B) -> bool {
+    return parallel(a, b) && (b.origin() - a.origin()).cross(a.direction()) == 0;
+}
+
+fn intersect_bounds(a: Bounds, b: Bounds) -> Option<Bounds> {
+    let low = match (a.0, b.0) {
+        (Some(x), Some(y)) => Some(x.max(y)),
+        (x, y) => x.or(y),
+    };
+    let high = match (a.1, b.1) {
+        (Some(x), Some(y)) => Some(x.min(y)),
+        (x, y) => x.or(y),
+    };
+    if let (Some(low), Some(high)) = (low, high) {
+        if low > high {
+            return None;
+        }
+    }
+    return Some((low, high));
+}
+
+fn contains(bounds: Bounds, t: Ratio) -> bool {
+    return bounds.0.is_none_or(|low| low <= t) && bounds.1.is_none_or(|high| t <= high);
+}
+
+/// Whether the part of `path` within `bounds` touches the square `min..=max`.
+fn clip<A: Linear>(path: &A, bounds: Bounds, min: i128, max: i128) -> bool {
+    let mut bounds = bounds;
+    let (origin, direction) = (path.origin(), path.direction());
+    for (o, d) in [(origin.x, direction.x), (origin.y, direction.y)] {
+        if d == 0 {
+            if !(min..=max).contains(&o) {
+                return false;
+            }
+            continue;
+        }
+        let enter = Ratio::new(min - o, d).unwrap();
+        let leave = Ratio::new(max - o, d).unwrap();
+        let axis = (Some(enter.min(leave)), Some(enter.max(leave)));
+        match intersect_bounds(bounds, axis) {
+            Some(clipped) => bounds = clipped,
+            None => return false,
+        }
+    }
+    return true;
+}
+
+/// The exact intersection of two lines, rays or segments.
+pub fn intersect<A: Linear, B: Linear>(a: &A, b: &B) -> Intersection {
+    let (p1, d1) = (a.origin(), a.direction());
+    let (p2, d2) = (b.origin(), b.direction());
+    let offset = p2 - p1;
+    let cross = d1.cross(d2);
+
+    if cross != 0 {
+        let t = Ratio::new(offset.cross(d2), cross).unwrap();
+        let s = Ratio::new(offset.cross(d1), cross).unwrap();
+        if contains(a.bounds(), t) && contains(b.bounds(), s) {
+            return Intersection::Point(a.at(t));
+        }
+        return Intersection::None;
+    }
+    if !collinear(a, b) {
+        return Intersection::None;
+    }
+
+    // b's point at s sits at t = (offset·d1 + s·(d2·d1)) / (d1·d1) on a
+    let length = Ratio::from(d1.dot(d1));
+    let shift = Ratio::from(offset.dot(d1)) / length;
+    let scale = Ratio::from(d2.dot(d1)) / length;
+    let map = |s: Option<Ratio>| s.map(|s| shift + s * scale);
+    let (low, high) = b.bounds();
+    let mapped = if scale > Ratio::ZERO {
+        (map(low), map(high))
+    } else {
+        (map(high), map(low))
+    };
+    return match intersect_bounds(a.bounds(), mapped) {
+        None => Intersection::None,
+        Some((Some(low), Some(high))) if low == high => Intersection::Point(a.at(low)),
+        Some((low, high)) => Intersection::Overlap(low.map(|t| a.at(t)), high.map(|t| a.at(t))),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: i128, y: i128) -> Point {
+        return Point::new(x, y);
+    }
+
+    fn r(numer: i128, denom: i128) -> Ratio {
+        return Ratio::new(numer, denom).unwrap();
+    }
+
+    // position and velocity of the first hailstones of the 2023 day 24 example
+    const HAIL: [(i128, i128, i128, i128); 5] = [
+        (19, 13, -2, 1),
+        (18, 19, -1, -1),
+        (20, 25, -2, -2),
+        (12, 31, -1, -2),
+        (20, 19, 1, -5),
+    ];
+
+    #[test]
+    fn test_intersect() {
+        let a = Line::new(p(0, 0), p(2, 1));
+        let b = Line::new(p(0, 3), p(1, -1));
+        assert_eq!(
+            Intersection::Point(RationalPoint {
+                x: r(2, 1),
+                y: r(1, 1)
+            }),
+            intersect(&a, &b)
+        );
+        let c = Line::new(p(1, 1), p(3, 1));
+        let point = match intersect(&a, &c) {
+            Intersection::Point(point) => point,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!((r(4, 1), r(2, 1)), (point.x, point.y));
+
+        assert!(parallel(&a, &Line::new(p(0, 1), p(-4, -2))));
+        assert!(!collinear(&a, &Line::new(p(0, 1), p(-4, -2))));
+        assert!(collinear(&a, &Segment::new(p(4, 2), p(6, 3))));
+
+        let segment = Segment::new(p(0, 3), p(1, 2));
+        assert_eq!(Intersection::None, intersect(&a, &segment));
+        let ray = Ray::new(p(3, 0), p(-1, 1));
+        assert_eq!(
+            Intersection::Point(RationalPoint {
+                x: r(2, 1),
+                y: r(1, 1)
+            }),
+            intersect(&a, &ray)
+        );
+        assert_eq!(
+            Intersection::None,
+            intersect(&Ray::new(p(3, 0), p(1, -1)), &a)
+        );
+    }
+
+    #[test]
+    fn test_overlap() {
+        let segment = Segment::new(p(0, 0), p(4, 2));
+        let other = Segment::new(p(6, 3), p(2, 1));
+        assert_eq!(
+            Intersection::Overlap(Some(p(2, 1).into()), Some(p(4, 2).into())),
+            intersect(&segment, &other)
+        );
+        let touching = Segment::new(p(4, 2), p(8, 4));
+        assert_eq!(
+            Intersection::Point(p(4, 2).into()),
+            intersect(&segment, &touching)
+        );
+        let ray = Ray::new(p(2, 1), p(-2, -1));
+        assert_eq!(
+            Intersection::Overlap(Some(p(0, 0).into()), Some(p(2, 1).into())),
+            intersect(&segment, &ray)
+        );
+        let line = Line::new(p(100, 50), p(2, 1));
+        assert_eq!(
+            Intersection::Overlap(Some(p(2, 1).into()), None),
+            intersect(&Ray::new(p(2, 1), p(4, 2)), &line)
+        );
+        assert_eq!(
+            Intersection::None,
+            intersect(&segment, &Segment::new(p(6, 3), p(8, 4)))
+        );
+    }
+
+    #[test]
+    fn test_future_within() {
+        let rays: Vec<Ray> = HAIL
+            .iter()
+            .map(|&(x, y, dx, dy)| Ray::new(p(x, y), p(dx, dy)))
+            .collect();
+        let mut count = 0;
+        for (i, a) in rays.iter().enumerate() {
+            for b in &rays[i + 1..] {
+                if a.future_intersection_within(b, 7, 27).is_some() {
+                    count += 1;
+                }
+            }
+        }
+        assert_eq!(2, count);
+
+        let hit = rays[0].future_intersection_within(&rays[1], 7, 27);
+        assert_eq!(
+            Some(Intersection::Point(RationalPoint {
+                x: r(43, 3),
+                y: r(46, 3)
+            })),
+            hit
+        );
+        // same paths, the shared stretch starts outside the area but reaches into it
+        let behind = Ray::new(p(0, 0), p(1, 1));
+        let ahead = Ray::new(p(-10, -10), p(2, 2));
+        assert!(behind.future_intersection_within(&ahead, 5, 6).is_some());
+        assert!(behind.future_intersection_within(&ahead, -6, -5).is_none());
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // coordinates like the real puzzle input, the products exceed 64 bits
+        let a = Ray::new(p(230027994633462, 224850233272831), p(-103, 57));
+        let b = Ray::new(p(213762157019377, 204038908928791), p(-184, 110));
+        let Intersection::Point(point) = intersect(&a, &b) else {
+            panic!("the paths should cross");
+        };
+        assert_eq!(r(-192512293827227063, 421), point.x);
+        assert_eq!(r(254789933988164086, 421), point.y);
+        assert!(a
+            .future_intersection_within(&b, -500000000000000, 700000000000000)
+            .is_some());
+        assert!(a
+            .future_intersection_within(&b, 200000000000000, 400000000000000)
+            .is_none());
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use super::{GraphError, NodeIndex, UndirectedGraph};
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    pub fn new(size: usize) -> Self {
+        return Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+        };
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // path compression
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        return root;
+    }
+
+    /// Returns `false` if `a` and `b` already were in the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        match self.rank[ra].cmp(&self.rank[rb]) {
+            std::cmp::Ordering::Less => self.parent[ra] = rb,
+            std::cmp::Ordering::Greater => self.parent[rb] = ra,
+            std::cmp::Ordering::Equal => {
+                self.parent[rb] = ra;
+                self.rank[ra] += 1;
+            }
+        }
+        return true;
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        return self.find(a) == self.find(b);
+    }
+}
+
+impl<N, E> UndirectedGraph<N, E> {
+    fn union_find(&self) -> UnionFind {
+        let mut sets = UnionFind::new(self.nodes.len());
+        for edge in &self.edges {
+            sets.union(edge.a, edge.b);
+        }
+        return sets;
+    }
+
+    /// Every component as a sorted list of its nodes, ordered by their smallest node.
+    pub fn connected_components(&self) -> Vec<Vec<NodeIndex>> {
+        let mut sets = self.union_find();
+        let mut slot: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut components: Vec<Vec<NodeIndex>> = Vec::new();
+        for node in 0..self.nodes.len() {
+            let root = sets.find(node);
+            match slot[root] {
+                Some(i) => components[i].push(node),
+                None => {
+                    slot[root] = Some(components.len());
+                    components.push(vec![node]);
+                }
+            }
+        }
+        return components;
+    }
+
+    pub fn is_connected(&self, a: NodeIndex, b: NodeIndex) -> Result<bool, GraphError> {
+        self.get_node(a)?;
+        self.get_node(b)?;
+        return Ok(self.union_find().connected(a, b));
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        let mut sets = UnionFind::new(self.nodes.len());
+        return self.edges.iter().any(|edge| !sets.union(edge.a, edge.b));
+    }
+
+    /// The shortest cycle through `node`, starting with `node` and listing every
+    /// other node of the cycle once, in walking order.
+    pub fn cycle_through(&self, node: NodeIndex) -> Result<Vec<NodeIndex>, GraphError> {
+        self.get_node(node)?;
+        let adj = self.adjacency();
+        let mut best: Option<Vec<NodeIndex>> = None;
+
+        for &(first, via) in &adj[node] {
+            if first == node {
+                return Ok(vec![node]);
+            }
+
+            // walk back to `node` without taking the edge we left through
+            let mut parent: Vec<Option<NodeIndex>> = vec![None; self.nodes.len()];
+            let mut seen = vec![false; self.nodes.len()];
+            let mut queue = VecDeque::from([first]);
+            seen[node] = true;
+            seen[first] = true;
+
+            let mut last: Option<NodeIndex> = None;
+            'search: while let Some(current) = queue.pop_front() {
+                for &(next, edge) in &adj[current] {
+                    if edge == via {
+                        continue;
+                    }
+                    if next == node {
+                        last = Some(current);
+                        break 'search;
+                    }
+                    if !seen[next] {
+                        seen[next] = true;
+                        parent[next] = Some(current);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            if let Some(last) = last {
+                let mut cycle = vec![last];
+                while let Some(p) = parent[*cycle.last().unwrap()] {
+                    cycle.push(p);
+                }
+                cycle.push(node);
+                cycle.reverse();
+                if best.as_ref().is_none_or(|b| cycle.len() < b.len()) {
+                    best = Some(cycle);
+                }
+            }
+        }
+
+        return best.ok_or(GraphError::NoCycle(node));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_with_tail() -> UndirectedGraph<(), ()> {
+        let mut graph = UndirectedGraph::new();
+        for _ in 0..6 {
+            graph.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0), (3, 4)] {
+            graph.add_edge(a, b, ()).unwrap();
+        }
+        return graph;
+    }
+
+    #[test]
+    fn test_components() {
+        let graph = square_with_tail();
+        assert_eq!(vec![vec![0, 1, 2, 3, 4], vec![5]], graph.connected_components());
+        assert!(graph.is_connected(0, 4).unwrap());
+        assert!(!graph.is_connected(0, 5).unwrap());
+        assert!(graph.has_cycle());
+    }
+
+    #[test]
+    fn test_cycle_through() {
+        let graph = square_with_tail();
+        assert_eq!(vec![1, 0, 3, 2], graph.cycle_through(1).unwrap());
+        assert!(matches!(graph.cycle_through(4), Err(GraphError::NoCycle(4))));
+    }
+}
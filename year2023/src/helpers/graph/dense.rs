@@ -0,0 +1,243 @@
+use super::{GraphError, NodeIndex, UndirectedGraph};
+use crate::helpers::math::{checked, MathError};
+
+/// Undirected graph stored as a bitset adjacency matrix plus a weight matrix,
+/// meant for a few hundred nodes.
+#[derive(Debug, Clone)]
+pub struct DenseGraph {
+    size: usize,
+    words: usize,
+    adjacency: Vec<u64>,
+    weights: Vec<Option<u64>>,
+}
+
+/// Distances between every pair of nodes, `None` where no path exists.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceTable {
+    size: usize,
+    distances: Vec<Option<u64>>,
+}
+
+impl DistanceTable {
+    pub fn size(&self) -> usize {
+        return self.size;
+    }
+
+    pub fn get(&self, a: NodeIndex, b: NodeIndex) -> Option<u64> {
+        if a >= self.size || b >= self.size {
+            return None;
+        }
+        return self.distances[a * self.size + b];
+    }
+
+    /// A smaller table between just `nodes`, the i-th entry of `nodes` becoming node i.
+    pub fn restrict(&self, nodes: &[NodeIndex]) -> Result<DistanceTable, GraphError> {
+        if let Some(&node) = nodes.iter().find(|&&n| n >= self.size) {
+            return Err(GraphError::NodeIndexOutOfBounds(node));
+        }
+        return Ok(DistanceTable {
+            size: nodes.len(),
+            distances: nodes
+                .iter()
+                .flat_map(|&a| nodes.iter().map(move |&b| (a, b)))
+                .map(|(a, b)| self.distances[a * self.size + b])
+                .collect(),
+        });
+    }
+}
+
+impl DenseGraph {
+    pub fn new(size: usize) -> Self {
+        let words = size.div_ceil(64);
+        return Self {
+            size,
+            words,
+            adjacency: vec![0; size * words],
+            weights: vec![None; size * size],
+        };
+    }
+
+    pub fn node_count(&self) -> usize {
+        return self.size;
+    }
+
+    /// Parallel edges collapse into the lightest one.
+    pub fn add_edge(&mut self, a: NodeIndex, b: NodeIndex, weight: u64) -> Result<(), GraphError> {
+        for node in [a, b] {
+            if node >= self.size {
+                return Err(GraphError::NodeIndexOutOfBounds(node));
+            }
+        }
+        for (from, to) in [(a, b), (b, a)] {
+            self.adjacency[from * self.words + to / 64] |= 1 << (to % 64);
+            let w = &mut self.weights[from * self.size + to];
+            *w = Some(w.map_or(weight, |old| old.min(weight)));
+        }
+        return Ok(());
+    }
+
+    pub fn has_edge(&self, a: NodeIndex, b: NodeIndex) -> bool {
+        if a >= self.size || b >= self.size {
+            return false;
+        }
+        return self.adjacency[a * self.words + b / 64] & (1 << (b % 64)) != 0;
+    }
+
+    pub fn weight(&self, a: NodeIndex, b: NodeIndex) -> Option<u64> {
+        if a >= self.size || b >= self.size {
+            return None;
+        }
+        return self.weights[a * self.size + b];
+    }
+
+    pub fn neighbors(
+        &self,
+        node: NodeIndex,
+    ) -> Result<impl Iterator<Item = NodeIndex> + '_, GraphError> {
+        if node >= self.size {
+            return Err(GraphError::NodeIndexOutOfBounds(node));
+        }
+        let row = &self.adjacency[node * self.words..(node + 1) * self.words];
+        return Ok(row.iter().enumerate().flat_map(|(i, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| i * 64 + bit)
+        }));
+    }
+
+    /// All-pairs shortest paths over the edge weights. Fails if a shortest path is longer
+    /// than `u64::MAX`.
+    pub fn floyd_warshall(&self) -> Result<DistanceTable, MathError> {
+        let n = self.size;
+        // a shortest path has fewer than n edges, so its length always fits into a u128
+        let mut distances: Vec<Option<u128>> =
+            self.weights.iter().map(|w| w.map(u128::from)).collect();
+        for node in 0..n {
+            distances[node * n + node] = Some(0);
+        }
+        for k in 0..n {
+            for i in 0..n {
+                let Some(ik) = distances[i * n + k] else {
+                    continue;
+                };
+                for j in 0..n {
+                    if let Some(kj) = distances[k * n + j] {
+                        let through = ik + kj;
+                        if distances[i * n + j].is_none_or(|d| through < d) {
+                            distances[i * n + j] = Some(through);
+                        }
+                    }
+                }
+            }
+        }
+        let distances = distances
+            .into_iter()
+            .map(|d| d.map(|d| checked(u64::try_from(d).ok())).transpose())
+            .collect::<Result<_, _>>()?;
+        return Ok(DistanceTable { size: n, distances });
+    }
+
+    /// All-pairs hop counts, one bitset breadth first search per node.
+    pub fn bfs_distances(&self) -> DistanceTable {
+        let n = self.size;
+        let mut distances = vec![None; n * n];
+        for source in 0..n {
+            let mut seen = vec![0_u64; self.words];
+            let mut frontier = vec![0_u64; self.words];
+            seen[source / 64] |= 1 << (source % 64);
+            frontier[source / 64] |= 1 << (source % 64);
+
+            let mut depth = 0;
+            while frontier.iter().any(|&w| w != 0) {
+                let mut next = vec![0_u64; self.words];
+                for (i, &word) in frontier.iter().enumerate() {
+                    for bit in (0..64).filter(|bit| word & (1 << bit) != 0) {
+                        let node = i * 64 + bit;
+                        distances[source * n + node] = Some(depth);
+                        let row = &self.adjacency[node * self.words..(node + 1) * self.words];
+                        for (w, &adjacent) in row.iter().enumerate() {
+                            next[w] |= adjacent & !seen[w];
+                        }
+                    }
+                }
+                for (w, &word) in next.iter().enumerate() {
+                    seen[w] |= word;
+                }
+                frontier = next;
+                depth += 1;
+            }
+        }
+        return DistanceTable { size: n, distances };
+    }
+}
+
+impl<N, E> UndirectedGraph<N, E> {
+    pub fn to_dense<F: Fn(&E) -> u64>(&self, weight: F) -> DenseGraph {
+        let mut dense = DenseGraph::new(self.nodes.len());
+        for edge in &self.edges {
+            dense.add_edge(edge.a, edge.b, weight(&edge.inner)).unwrap();
+        }
+        return dense;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_graph() -> UndirectedGraph<(), u64> {
+        let mut graph = UndirectedGraph::new();
+        for _ in 0..70 {
+            graph.add_node(());
+        }
+        for node in 0..68 {
+            graph.add_edge(node, node + 1, 2).unwrap();
+        }
+        graph.add_edge(0, 68, 200).unwrap();
+        return graph;
+    }
+
+    #[test]
+    fn test_dense_graph() {
+        let dense = path_graph().to_dense(|&w| w);
+        assert!(dense.has_edge(68, 0));
+        assert!(!dense.has_edge(0, 69));
+        assert_eq!(vec![1, 68], dense.neighbors(0).unwrap().collect::<Vec<_>>());
+        assert!(matches!(
+            dense.neighbors(70),
+            Err(GraphError::NodeIndexOutOfBounds(70))
+        ));
+        assert_eq!(Some(200), dense.weight(0, 68));
+    }
+
+    #[test]
+    fn test_all_pairs() {
+        let dense = path_graph().to_dense(|&w| w);
+        let weighted = dense.floyd_warshall().unwrap();
+        assert_eq!(Some(136), weighted.get(0, 68));
+        assert_eq!(Some(0), weighted.get(5, 5));
+        assert_eq!(None, weighted.get(0, 69));
+
+        let hops = dense.bfs_distances();
+        assert_eq!(Some(1), hops.get(0, 68));
+        assert_eq!(Some(34), hops.get(0, 34));
+        assert_eq!(None, hops.get(69, 3));
+
+        let small = weighted.restrict(&[68, 0, 10]).unwrap();
+        assert_eq!(Some(136), small.get(0, 1));
+        assert_eq!(Some(20), small.get(1, 2));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut dense = DenseGraph::new(3);
+        dense.add_edge(0, 1, u64::MAX).unwrap();
+        dense.add_edge(1, 2, u64::MAX / 2).unwrap();
+        assert_eq!(Err(MathError::Overflow), dense.floyd_warshall());
+        let mut dense = DenseGraph::new(3);
+        dense.add_edge(0, 1, u64::MAX / 2).unwrap();
+        dense.add_edge(1, 2, u64::MAX / 2 + 1).unwrap();
+        // going back and forth doesn't fit, but is never the shortest way
+        assert_eq!(Some(u64::MAX), dense.floyd_warshall().unwrap().get(2, 0));
+    }
+}
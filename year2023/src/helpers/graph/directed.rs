@@ -0,0 +1,145 @@
+use super::{Edge, EdgeIndex, GraphError, Node, NodeIndex};
+
+/// Like [`super::UndirectedGraph`], but every edge points from `a` to `b`.
+#[derive(Debug)]
+pub struct DirectedGraph<N, E> {
+    pub(super) nodes: Vec<Node<N>>,
+    pub(super) edges: Vec<Edge<E>>,
+}
+
+impl<N, E> Default for DirectedGraph<N, E> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<N, E> DirectedGraph<N, E> {
+    pub fn new() -> Self {
+        return Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+    }
+
+    pub fn get_node(&self, node: NodeIndex) -> Result<&N, GraphError> {
+        return match self.nodes.get(node) {
+            Some(n) => Ok(&n.inner),
+            None => Err(GraphError::NodeIndexOutOfBounds(node)),
+        };
+    }
+
+    pub fn get_mut_node(&mut self, node: NodeIndex) -> Result<&mut N, GraphError> {
+        return match self.nodes.get_mut(node) {
+            Some(n) => Ok(&mut n.inner),
+            None => Err(GraphError::NodeIndexOutOfBounds(node)),
+        };
+    }
+
+    pub fn get_edge(&self, edge: EdgeIndex) -> Result<&E, GraphError> {
+        return match self.edges.get(edge) {
+            Some(e) => Ok(&e.inner),
+            None => Err(GraphError::EdgeIndexOutOfBounds(edge)),
+        };
+    }
+
+    pub fn get_mut_edge(&mut self, edge: EdgeIndex) -> Result<&mut E, GraphError> {
+        return match self.edges.get_mut(edge) {
+            Some(e) => Ok(&mut e.inner),
+            None => Err(GraphError::EdgeIndexOutOfBounds(edge)),
+        };
+    }
+
+    pub fn edge_endpoints(&self, edge: EdgeIndex) -> Result<(NodeIndex, NodeIndex), GraphError> {
+        return match self.edges.get(edge) {
+            Some(e) => Ok((e.a, e.b)),
+            None => Err(GraphError::EdgeIndexOutOfBounds(edge)),
+        };
+    }
+
+    pub fn node_count(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    pub fn edge_count(&self) -> usize {
+        return self.edges.len();
+    }
+
+    pub fn add_node(&mut self, data: N) -> NodeIndex {
+        let id = self.nodes.len();
+        self.nodes.push(Node { id, inner: data });
+        return id;
+    }
+
+    pub fn add_edge(
+        &mut self,
+        from: NodeIndex,
+        to: NodeIndex,
+        data: E,
+    ) -> Result<EdgeIndex, GraphError> {
+        self.get_node(from)?;
+        self.get_node(to)?;
+        let id = self.edges.len();
+        self.edges.push(Edge {
+            id,
+            inner: data,
+            a: from,
+            b: to,
+        });
+        return Ok(id);
+    }
+
+    pub fn successors(
+        &self,
+        node: NodeIndex,
+    ) -> Result<impl Iterator<Item = NodeIndex> + '_, GraphError> {
+        self.get_node(node)?;
+        return Ok(self
+            .edges
+            .iter()
+            .filter(move |edge| edge.a == node)
+            .map(|edge| edge.b));
+    }
+
+    pub fn predecessors(
+        &self,
+        node: NodeIndex,
+    ) -> Result<impl Iterator<Item = NodeIndex> + '_, GraphError> {
+        self.get_node(node)?;
+        return Ok(self
+            .edges
+            .iter()
+            .filter(move |edge| edge.b == node)
+            .map(|edge| edge.a));
+    }
+
+    // (successor, edge) pairs for every node
+    pub(super) fn out_adjacency(&self) -> Vec<Vec<(NodeIndex, EdgeIndex)>> {
+        let mut adj = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            adj[edge.a].push((edge.b, edge.id));
+        }
+        return adj;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directed_graph() {
+        let mut graph: DirectedGraph<char, ()> = DirectedGraph::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let c = graph.add_node('c');
+        graph.add_edge(a, b, ()).unwrap();
+        graph.add_edge(a, c, ()).unwrap();
+        graph.add_edge(c, b, ()).unwrap();
+        assert_eq!(vec![b, c], graph.successors(a).unwrap().collect::<Vec<_>>());
+        assert_eq!(
+            vec![a, c],
+            graph.predecessors(b).unwrap().collect::<Vec<_>>()
+        );
+        assert!(graph.add_edge(a, 3, ()).is_err());
+    }
+}
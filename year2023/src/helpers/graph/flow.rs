@@ -0,0 +1,273 @@
+use std::collections::VecDeque;
+
+use super::{directed::DirectedGraph, EdgeIndex, GraphError, NodeIndex, UndirectedGraph};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow {
+    pub value: u64,
+    /// Flow along every edge, negative when it runs from `b` to `a` of an undirected edge.
+    pub flow: Vec<i64>,
+    /// Nodes still reachable from the source in the residual graph, i.e. a minimum s-t cut.
+    pub source_side: Vec<NodeIndex>,
+    pub cut_edges: Vec<EdgeIndex>,
+}
+
+// Dinic's algorithm; arc `2k` is the forward arc of edge `k`, arc `2k + 1` its reverse
+struct Dinic {
+    adj: Vec<Vec<usize>>,
+    to: Vec<NodeIndex>,
+    capacity: Vec<u64>,
+    level: Vec<Option<usize>>,
+    next_arc: Vec<usize>,
+}
+
+impl Dinic {
+    fn new(nodes: usize) -> Self {
+        return Self {
+            adj: vec![Vec::new(); nodes],
+            to: Vec::new(),
+            capacity: Vec::new(),
+            level: vec![None; nodes],
+            next_arc: vec![0; nodes],
+        };
+    }
+
+    fn add_arc(&mut self, a: NodeIndex, b: NodeIndex, forward: u64, backward: u64) {
+        self.adj[a].push(self.to.len());
+        self.to.push(b);
+        self.capacity.push(forward);
+        self.adj[b].push(self.to.len());
+        self.to.push(a);
+        self.capacity.push(backward);
+    }
+
+    fn bfs(&mut self, source: NodeIndex) {
+        self.level.fill(None);
+        self.level[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &arc in &self.adj[node] {
+                let next = self.to[arc];
+                if self.capacity[arc] > 0 && self.level[next].is_none() {
+                    self.level[next] = self.level[node].map(|l| l + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    fn dfs(&mut self, node: NodeIndex, sink: NodeIndex, limit: u64) -> u64 {
+        if node == sink {
+            return limit;
+        }
+        while self.next_arc[node] < self.adj[node].len() {
+            let arc = self.adj[node][self.next_arc[node]];
+            let next = self.to[arc];
+            if self.capacity[arc] > 0 && self.level[next] == self.level[node].map(|l| l + 1) {
+                let pushed = self.dfs(next, sink, limit.min(self.capacity[arc]));
+                if pushed > 0 {
+                    self.capacity[arc] -= pushed;
+                    self.capacity[arc ^ 1] += pushed;
+                    return pushed;
+                }
+            }
+            self.next_arc[node] += 1;
+        }
+        return 0;
+    }
+
+    fn run(&mut self, source: NodeIndex, sink: NodeIndex) -> u64 {
+        let mut total = 0;
+        loop {
+            self.bfs(source);
+            if self.level[sink].is_none() {
+                return total;
+            }
+            self.next_arc.fill(0);
+            loop {
+                let pushed = self.dfs(source, sink, u64::MAX);
+                if pushed == 0 {
+                    break;
+                }
+                total += pushed;
+            }
+        }
+    }
+}
+
+fn max_flow(
+    nodes: usize,
+    arcs: Vec<(NodeIndex, NodeIndex, u64, u64)>,
+    source: NodeIndex,
+    sink: NodeIndex,
+) -> MaxFlow {
+    let mut dinic = Dinic::new(nodes);
+    for &(a, b, forward, backward) in &arcs {
+        dinic.add_arc(a, b, forward, backward);
+    }
+    let value = dinic.run(source, sink);
+
+    // the last bfs left every node reachable in the residual graph with a level
+    let flow: Vec<i64> = arcs
+        .iter()
+        .enumerate()
+        .map(|(k, &(_, _, forward, _))| forward as i64 - dinic.capacity[2 * k] as i64)
+        .collect();
+    let source_side: Vec<NodeIndex> = (0..nodes).filter(|&n| dinic.level[n].is_some()).collect();
+    let cut_edges: Vec<EdgeIndex> = arcs
+        .iter()
+        .enumerate()
+        .filter(|(_, &(a, b, _, backward))| {
+            let (from_a, from_b) = (dinic.level[a].is_some(), dinic.level[b].is_some());
+            return (from_a && !from_b) || (from_b && !from_a && backward > 0);
+        })
+        .map(|(k, _)| k)
+        .collect();
+
+    return MaxFlow {
+        value,
+        flow,
+        source_side,
+        cut_edges,
+    };
+}
+
+impl<N, E> DirectedGraph<N, E> {
+    /// Maximum flow from `source` to `sink` (Dinic), with edge capacities read from `E`.
+    pub fn max_flow<F: Fn(&E) -> u64>(
+        &self,
+        source: NodeIndex,
+        sink: NodeIndex,
+        capacity: F,
+    ) -> Result<MaxFlow, GraphError> {
+        self.get_node(source)?;
+        self.get_node(sink)?;
+        if source == sink {
+            return Err(GraphError::SourceIsSink(source));
+        }
+        let arcs = self
+            .edges
+            .iter()
+            .map(|edge| (edge.a, edge.b, capacity(&edge.inner), 0))
+            .collect();
+        return Ok(max_flow(self.nodes.len(), arcs, source, sink));
+    }
+}
+
+impl<N, E> UndirectedGraph<N, E> {
+    /// Maximum flow from `source` to `sink` (Dinic), every edge carrying up to its
+    /// capacity in either direction.
+    pub fn max_flow<F: Fn(&E) -> u64>(
+        &self,
+        source: NodeIndex,
+        sink: NodeIndex,
+        capacity: F,
+    ) -> Result<MaxFlow, GraphError> {
+        self.get_node(source)?;
+        self.get_node(sink)?;
+        if source == sink {
+            return Err(GraphError::SourceIsSink(source));
+        }
+        let arcs = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let c = capacity(&edge.inner);
+                return (edge.a, edge.b, c, c);
+            })
+            .collect();
+        return Ok(max_flow(self.nodes.len(), arcs, source, sink));
+    }
+
+    /// A maximum matching between the nodes in `left` and all other nodes.
+    pub fn bipartite_matching(&self, left: &[NodeIndex]) -> Result<Vec<EdgeIndex>, GraphError> {
+        let n = self.nodes.len();
+        let mut is_left = vec![false; n];
+        for &node in left {
+            self.get_node(node)?;
+            is_left[node] = true;
+        }
+
+        // two extra nodes: the source feeds every left node, every right node drains to the sink
+        let (source, sink) = (n, n + 1);
+        let mut arcs: Vec<(NodeIndex, NodeIndex, u64, u64)> = Vec::new();
+        for edge in &self.edges {
+            let arc = match (is_left[edge.a], is_left[edge.b]) {
+                (true, false) => (edge.a, edge.b, 1, 0),
+                (false, true) => (edge.b, edge.a, 1, 0),
+                _ => return Err(GraphError::NotBipartite(edge.id)),
+            };
+            arcs.push(arc);
+        }
+        for (node, &on_left) in is_left.iter().enumerate() {
+            arcs.push(match on_left {
+                true => (source, node, 1, 0),
+                false => (node, sink, 1, 0),
+            });
+        }
+
+        let result = max_flow(n + 2, arcs, source, sink);
+        return Ok((0..self.edges.len())
+            .filter(|&e| result.flow[e] != 0)
+            .collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_flow() {
+        let mut graph: DirectedGraph<(), u64> = DirectedGraph::new();
+        for _ in 0..6 {
+            graph.add_node(());
+        }
+        for (a, b, c) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 2, 10),
+            (2, 1, 4),
+            (1, 3, 12),
+            (3, 2, 9),
+            (2, 4, 14),
+            (4, 3, 7),
+            (3, 5, 20),
+            (4, 5, 4),
+        ] {
+            graph.add_edge(a, b, c).unwrap();
+        }
+        let result = graph.max_flow(0, 5, |&c| c).unwrap();
+        assert_eq!(23, result.value);
+        assert_eq!(vec![0, 1, 2, 4], result.source_side);
+        assert_eq!(vec![4, 7, 9], result.cut_edges);
+        assert!(graph.max_flow(0, 0, |&c| c).is_err());
+    }
+
+    #[test]
+    fn test_undirected_max_flow() {
+        let mut graph: UndirectedGraph<(), ()> = UndirectedGraph::new();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        for (a, b) in [(0, 1), (0, 2), (1, 3), (2, 3), (2, 1)] {
+            graph.add_edge(a, b, ()).unwrap();
+        }
+        let result = graph.max_flow(3, 0, |_| 1).unwrap();
+        assert_eq!(2, result.value);
+        assert_eq!(-1, result.flow[0]);
+    }
+
+    #[test]
+    fn test_bipartite_matching() {
+        let mut graph: UndirectedGraph<(), ()> = UndirectedGraph::new();
+        for _ in 0..6 {
+            graph.add_node(());
+        }
+        for (a, b) in [(0, 3), (0, 4), (1, 3), (2, 3), (2, 5)] {
+            graph.add_edge(a, b, ()).unwrap();
+        }
+        assert_eq!(3, graph.bipartite_matching(&[0, 1, 2]).unwrap().len());
+        assert!(graph.bipartite_matching(&[0, 3]).is_err());
+    }
+}
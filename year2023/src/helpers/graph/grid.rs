@@ -0,0 +1,148 @@
+use super::{NodeIndex, UndirectedGraph};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub fn opposite(self) -> Self {
+        return match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        };
+    }
+}
+
+/// Maps grid cells `(row, column)` to the nodes built from them and back.
+#[derive(Debug, Clone)]
+pub struct GridMap {
+    width: usize,
+    height: usize,
+    nodes: Vec<Option<NodeIndex>>,
+    cells: Vec<(usize, usize)>,
+}
+
+impl GridMap {
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn node(&self, row: usize, col: usize) -> Option<NodeIndex> {
+        if col >= self.width {
+            return None;
+        }
+        return *self.nodes.get(row * self.width + col)?;
+    }
+
+    pub fn cell(&self, node: NodeIndex) -> Option<(usize, usize)> {
+        return self.cells.get(node).copied();
+    }
+}
+
+/// Builds a graph with one node per passable cell. Horizontal and vertical neighbors
+/// `a` and `b` are joined when `connects(a, dir, b)` holds, `dir` pointing from `a` to `b`.
+pub fn grid_graph<T, R, P, C>(
+    rows: R,
+    passable: P,
+    connects: C,
+) -> (UndirectedGraph<T, ()>, GridMap)
+where
+    R: IntoIterator,
+    R::Item: IntoIterator<Item = T>,
+    P: Fn(&T) -> bool,
+    C: Fn(&T, Direction, &T) -> bool,
+{
+    let mut graph = UndirectedGraph::new();
+    let mut grid: Vec<Vec<Option<NodeIndex>>> = Vec::new();
+    let mut cells: Vec<(usize, usize)> = Vec::new();
+
+    for (r, row) in rows.into_iter().enumerate() {
+        let mut current: Vec<Option<NodeIndex>> = Vec::new();
+        for (c, cell) in row.into_iter().enumerate() {
+            if !passable(&cell) {
+                current.push(None);
+                continue;
+            }
+            let node = graph.add_node(cell);
+            cells.push((r, c));
+            current.push(Some(node));
+
+            let left = c.checked_sub(1).and_then(|l| current[l]);
+            if let Some(left) = left {
+                let (a, b) = (graph.get_node(left).unwrap(), graph.get_node(node).unwrap());
+                if connects(a, Direction::East, b) {
+                    graph.add_edge(left, node, ()).unwrap();
+                }
+            }
+            let top = grid.last().and_then(|prev| prev.get(c).copied().flatten());
+            if let Some(top) = top {
+                let (a, b) = (graph.get_node(top).unwrap(), graph.get_node(node).unwrap());
+                if connects(a, Direction::South, b) {
+                    graph.add_edge(top, node, ()).unwrap();
+                }
+            }
+        }
+        grid.push(current);
+    }
+
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let height = grid.len();
+    let mut nodes = vec![None; width * height];
+    for (r, row) in grid.iter().enumerate() {
+        for (c, &node) in row.iter().enumerate() {
+            nodes[r * width + c] = node;
+        }
+    }
+
+    return (
+        graph,
+        GridMap {
+            width,
+            height,
+            nodes,
+            cells,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_graph() {
+        let maze = "#.##\n#..#\n##.#";
+        let (graph, map) = grid_graph(maze.lines().map(str::bytes), |&c| c != b'#', |_, _, _| true);
+        assert_eq!(4, graph.node_count());
+        assert_eq!(3, graph.edge_count());
+        assert_eq!((4, 3), (map.width(), map.height()));
+        assert_eq!(None, map.node(0, 0));
+        assert_eq!(Some(2), map.node(1, 2));
+        assert_eq!(Some((2, 2)), map.cell(3));
+        assert_eq!(vec![2], graph.neighbors(3).unwrap().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_grid_graph_directions() {
+        let (graph, _) = grid_graph(
+            [[0, 1], [2, 3]],
+            |_| true,
+            |&a, dir, &b| match dir {
+                Direction::East => a + 1 == b,
+                _ => false,
+            },
+        );
+        assert_eq!(2, graph.edge_count());
+        assert_eq!(vec![0], graph.neighbors(1).unwrap().collect::<Vec<_>>());
+    }
+}
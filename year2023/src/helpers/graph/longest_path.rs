@@ -0,0 +1,259 @@
+use super::{EdgeIndex, GraphError, NodeIndex, UndirectedGraph};
+
+impl<N, E> UndirectedGraph<N, E> {
+    /// Replaces every chain of degree-2 nodes by a single edge weighted with the chain's
+    /// total weight. The nodes of the returned graph hold their index in `self`; nodes in
+    /// `keep` stay junctions even when they have degree 2.
+    pub fn contract_corridors<F: Fn(&E) -> u64>(
+        &self,
+        weight: F,
+        keep: &[NodeIndex],
+    ) -> Result<UndirectedGraph<NodeIndex, u64>, GraphError> {
+        for &node in keep {
+            self.get_node(node)?;
+        }
+
+        let adj = self.adjacency();
+        let mut junction: Vec<Option<NodeIndex>> = vec![None; self.nodes.len()];
+        let mut contracted = UndirectedGraph::new();
+        for node in 0..self.nodes.len() {
+            if adj[node].len() != 2 || keep.contains(&node) {
+                junction[node] = Some(contracted.add_node(node));
+            }
+        }
+
+        let mut used = vec![false; self.edges.len()];
+        let junctions: Vec<NodeIndex> = (0..self.nodes.len())
+            .filter(|&n| junction[n].is_some())
+            .collect();
+        // whatever is left after the junctions are a loop made only of degree-2 nodes
+        for node in junctions.into_iter().chain(0..self.nodes.len()) {
+            if junction[node].is_none() && adj[node].iter().any(|&(_, e)| !used[e]) {
+                junction[node] = Some(contracted.add_node(node));
+            }
+            let Some(from) = junction[node] else {
+                continue;
+            };
+
+            for &(first, edge) in &adj[node] {
+                if used[edge] {
+                    continue;
+                }
+                used[edge] = true;
+                let mut length = weight(&self.edges[edge].inner);
+                let mut current = first;
+                let mut via: EdgeIndex = edge;
+                while junction[current].is_none() {
+                    let &(next, e) = adj[current].iter().find(|&&(_, e)| e != via).unwrap();
+                    used[e] = true;
+                    length += weight(&self.edges[e].inner);
+                    current = next;
+                    via = e;
+                }
+                // a corridor back to where it started can never be part of a simple path
+                let to = junction[current].unwrap();
+                if to != from {
+                    contracted.add_edge(from, to, length)?;
+                }
+            }
+        }
+
+        return Ok(contracted);
+    }
+
+    /// Longest simple path from `from` to `to`, with its length and its nodes in order.
+    /// This is an exhaustive search, so contract the graph to its junctions first.
+    pub fn longest_path<F: Fn(&E) -> u64>(
+        &self,
+        from: NodeIndex,
+        to: NodeIndex,
+        weight: F,
+    ) -> Result<Option<(u64, Vec<NodeIndex>)>, GraphError> {
+        self.get_node(from)?;
+        self.get_node(to)?;
+
+        let adj: Vec<Vec<(NodeIndex, u64)>> = self
+            .adjacency()
+            .into_iter()
+            .map(|list| {
+                list.into_iter()
+                    .filter(|&(_, e)| self.edges[e].a != self.edges[e].b)
+                    .map(|(next, e)| (next, weight(&self.edges[e].inner)))
+                    .collect()
+            })
+            .collect();
+
+        // every node but the first is entered at most once, through its heaviest edge at best
+        let best_entry: Vec<u64> = adj
+            .iter()
+            .map(|list| list.iter().map(|&(_, w)| w).max().unwrap_or(0))
+            .collect();
+
+        // when the target has a single neighbor, reaching that neighbor ends the path
+        let mut target_neighbors: Vec<NodeIndex> = adj[to].iter().map(|&(n, _)| n).collect();
+        target_neighbors.sort_unstable();
+        target_neighbors.dedup();
+        let gate = match target_neighbors[..] {
+            [only] => Some(only),
+            _ => None,
+        };
+
+        let mut search = Search {
+            adj: &adj,
+            best_entry: &best_entry,
+            to,
+            gate,
+            visited: vec![0; self.nodes.len().div_ceil(64)],
+            path: vec![from],
+            best: None,
+        };
+        search.visit(from);
+        let remaining: u64 = best_entry.iter().sum::<u64>() - best_entry[from];
+        search.dfs(from, 0, remaining);
+
+        return Ok(search.best);
+    }
+}
+
+struct Search<'a> {
+    adj: &'a [Vec<(NodeIndex, u64)>],
+    best_entry: &'a [u64],
+    to: NodeIndex,
+    gate: Option<NodeIndex>,
+    visited: Vec<u64>,
+    path: Vec<NodeIndex>,
+    best: Option<(u64, Vec<NodeIndex>)>,
+}
+
+impl<'a> Search<'a> {
+    fn is_visited(&self, node: NodeIndex) -> bool {
+        return self.visited[node / 64] & (1 << (node % 64)) != 0;
+    }
+
+    fn visit(&mut self, node: NodeIndex) {
+        self.visited[node / 64] |= 1 << (node % 64);
+    }
+
+    fn leave(&mut self, node: NodeIndex) {
+        self.visited[node / 64] &= !(1 << (node % 64));
+    }
+
+    fn dfs(&mut self, node: NodeIndex, length: u64, remaining: u64) {
+        if node == self.to {
+            if self.best.as_ref().is_none_or(|(b, _)| length > *b) {
+                self.best = Some((length, self.path.clone()));
+            }
+            return;
+        }
+        if self
+            .best
+            .as_ref()
+            .is_some_and(|(b, _)| length + remaining <= *b)
+        {
+            return;
+        }
+
+        let forced = self.gate == Some(node);
+        let adj = self.adj;
+        for &(next, w) in &adj[node] {
+            if self.is_visited(next) || (forced && next != self.to) {
+                continue;
+            }
+            self.visit(next);
+            self.path.push(next);
+            self.dfs(next, length + w, remaining - self.best_entry[next]);
+            self.path.pop();
+            self.leave(next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::grid::grid_graph;
+    use super::*;
+
+    const TRAILS: &str = "#.#####\n#.....#\n#.#.#.#\n#.....#\n#####.#";
+
+    fn trail_graph(input: &str) -> (UndirectedGraph<u8, ()>, NodeIndex, NodeIndex) {
+        let (graph, cells) = grid_graph(
+            input.lines().map(str::bytes),
+            |&tile| tile != b'#',
+            |_, _, _| true,
+        );
+        let start = cells.node(0, 1).unwrap();
+        let end = cells.node(cells.height() - 1, cells.width() - 2).unwrap();
+        return (graph, start, end);
+    }
+
+    #[test]
+    fn test_contract_corridors() {
+        let mut graph: UndirectedGraph<(), u64> = UndirectedGraph::new();
+        for _ in 0..5 {
+            graph.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (2, 4)] {
+            graph.add_edge(a, b, 1).unwrap();
+        }
+        let contracted = graph.contract_corridors(|&w| w, &[]).unwrap();
+        assert_eq!(4, contracted.node_count());
+        assert_eq!(3, contracted.edge_count());
+        assert_eq!(2, *contracted.get_edge(0).unwrap());
+    }
+
+    #[test]
+    fn test_contract_loop_corridor() {
+        let mut graph: UndirectedGraph<(), u64> = UndirectedGraph::new();
+        for _ in 0..5 {
+            graph.add_node(());
+        }
+        // 0 -> 1 -> 2 -> 0 loops back to the junction at 0
+        for (a, b, w) in [(0, 1, 5), (1, 2, 5), (2, 0, 5), (0, 3, 1), (3, 4, 1)] {
+            graph.add_edge(a, b, w).unwrap();
+        }
+        let contracted = graph.contract_corridors(|&w| w, &[]).unwrap();
+        assert_eq!(2, contracted.node_count());
+        assert_eq!(1, contracted.edge_count());
+        assert_eq!(2, *contracted.get_edge(0).unwrap());
+        assert_eq!(
+            Some((2, vec![0, 1])),
+            contracted.longest_path(0, 1, |&w| w).unwrap()
+        );
+
+        // a cycle with no junction at all contracts to a single node
+        let mut ring: UndirectedGraph<(), u64> = UndirectedGraph::new();
+        for _ in 0..3 {
+            ring.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+            ring.add_edge(a, b, 1).unwrap();
+        }
+        let contracted = ring.contract_corridors(|&w| w, &[]).unwrap();
+        assert_eq!(1, contracted.node_count());
+        assert_eq!(0, contracted.edge_count());
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let (graph, start, end) = trail_graph(TRAILS);
+        assert_eq!(
+            12,
+            graph.longest_path(start, end, |_| 1).unwrap().unwrap().0
+        );
+
+        let junctions = graph.contract_corridors(|_| 1, &[start, end]).unwrap();
+        let find = |n: NodeIndex| {
+            (0..junctions.node_count())
+                .find(|&j| *junctions.get_node(j).unwrap() == n)
+                .unwrap()
+        };
+        let (length, path) = junctions
+            .longest_path(find(start), find(end), |&w| w)
+            .unwrap()
+            .unwrap();
+        assert_eq!(12, length);
+        assert_eq!(6, path.len());
+        assert_eq!(find(start), path[0]);
+        assert_eq!(find(end), *path.last().unwrap());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use super::{components::UnionFind, EdgeIndex, GraphError, NodeIndex, UndirectedGraph};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinCut {
+    pub weight: u64,
+    pub edges: Vec<EdgeIndex>,
+    /// The side containing node `0` comes first, both sides are sorted.
+    pub partition: (Vec<NodeIndex>, Vec<NodeIndex>),
+}
+
+// splitmix64, good enough to shuffle edges without pulling in a dependency
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return z ^ (z >> 31);
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        return ((self.next_u64() >> 11) as f64 + 1.0) / (1_u64 << 53) as f64;
+    }
+}
+
+impl<N, E> UndirectedGraph<N, E> {
+    fn cut_from_side<F: Fn(&E) -> u64>(&self, in_side: &[bool], weight: &F) -> MinCut {
+        let flip = !in_side[0];
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        for (node, &side) in in_side.iter().enumerate() {
+            if side != flip {
+                first.push(node);
+            } else {
+                second.push(node);
+            }
+        }
+        let edges: Vec<EdgeIndex> = self
+            .edges
+            .iter()
+            .filter(|edge| in_side[edge.a] != in_side[edge.b])
+            .map(|edge| edge.id)
+            .collect();
+        return MinCut {
+            weight: edges.iter().map(|&e| weight(&self.edges[e].inner)).sum(),
+            edges,
+            partition: (first, second),
+        };
+    }
+
+    /// Deterministic global minimum cut (Stoer–Wagner).
+    pub fn stoer_wagner<F: Fn(&E) -> u64>(&self, weight: F) -> Result<MinCut, GraphError> {
+        let n = self.nodes.len();
+        if n < 2 {
+            return Err(GraphError::TooFewNodes(n));
+        }
+
+        let mut adj: Vec<HashMap<usize, u64>> = vec![HashMap::new(); n];
+        for edge in self.edges.iter().filter(|edge| edge.a != edge.b) {
+            let w = weight(&edge.inner);
+            *adj[edge.a].entry(edge.b).or_insert(0) += w;
+            *adj[edge.b].entry(edge.a).or_insert(0) += w;
+        }
+
+        let mut members: Vec<Vec<NodeIndex>> = (0..n).map(|v| vec![v]).collect();
+        let mut merged = vec![false; n];
+        let mut best: Option<(u64, Vec<NodeIndex>)> = None;
+
+        for phase in 0..(n - 1) {
+            let start = (0..n).find(|&v| !merged[v]).unwrap();
+            let mut added = vec![false; n];
+            let mut tightness = vec![0_u64; n];
+            let mut heap = BinaryHeap::from([(0_u64, Reverse(start))]);
+            let mut order: Vec<usize> = Vec::with_capacity(n - phase);
+
+            while let Some((w, Reverse(v))) = heap.pop() {
+                if added[v] || w != tightness[v] {
+                    continue;
+                }
+                added[v] = true;
+                order.push(v);
+                for (&u, &uw) in &adj[v] {
+                    if !added[u] {
+                        tightness[u] += uw;
+                        heap.push((tightness[u], Reverse(u)));
+                    }
+                }
+            }
+
+            // a disconnected rest never got reached, which is a cut of weight 0
+            if order.len() < n - phase {
+                let mut in_side = vec![false; n];
+                for &v in &order {
+                    for &m in &members[v] {
+                        in_side[m] = true;
+                    }
+                }
+                return Ok(self.cut_from_side(&in_side, &weight));
+            }
+
+            let t = order[order.len() - 1];
+            let s = order[order.len() - 2];
+            if best.as_ref().is_none_or(|(w, _)| tightness[t] < *w) {
+                best = Some((tightness[t], members[t].clone()));
+            }
+
+            let moved = std::mem::take(&mut members[t]);
+            members[s].extend(moved);
+            merged[t] = true;
+            for (u, w) in std::mem::take(&mut adj[t]) {
+                adj[u].remove(&t);
+                if u != s {
+                    *adj[s].entry(u).or_insert(0) += w;
+                    *adj[u].entry(s).or_insert(0) += w;
+                }
+            }
+        }
+
+        let mut in_side = vec![false; n];
+        for m in best.unwrap().1 {
+            in_side[m] = true;
+        }
+        return Ok(self.cut_from_side(&in_side, &weight));
+    }
+
+    /// Randomized minimum cut (Karger), keeping the best of `trials` contractions.
+    pub fn karger<F: Fn(&E) -> u64>(
+        &self,
+        weight: F,
+        trials: usize,
+        seed: u64,
+    ) -> Result<MinCut, GraphError> {
+        let n = self.nodes.len();
+        if n < 2 {
+            return Err(GraphError::TooFewNodes(n));
+        }
+
+        let mut rng = SplitMix64(seed);
+        let mut best: Option<MinCut> = None;
+
+        for _ in 0..trials.max(1) {
+            // a weighted random order of the edges, heavier edges tend to come first
+            let mut order: Vec<(f64, EdgeIndex)> = self
+                .edges
+                .iter()
+                .map(|edge| {
+                    let w = weight(&edge.inner);
+                    let key = if w == 0 {
+                        -1.0
+                    } else {
+                        rng.next_f64().powf(1.0 / w as f64)
+                    };
+                    return (key, edge.id);
+                })
+                .collect();
+            order.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+            let mut sets = UnionFind::new(n);
+            let mut count = n;
+            for (_, e) in order {
+                if count == 2 {
+                    break;
+                }
+                if sets.union(self.edges[e].a, self.edges[e].b) {
+                    count -= 1;
+                }
+            }
+
+            let root = sets.find(0);
+            let in_side: Vec<bool> = (0..n).map(|v| sets.find(v) == root).collect();
+            let cut = self.cut_from_side(&in_side, &weight);
+            if best.as_ref().is_none_or(|b| cut.weight < b.weight) {
+                best = Some(cut);
+            }
+        }
+
+        return Ok(best.unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse::parse_adjacency_list;
+    use super::*;
+
+    const EXAMPLE_2023_25: &str = "jqt: rhn xhk nvd\nrsh: frs pzl lsr\nxhk: hfx\ncmg: qnr nvd lhk bvb\nrhn: xhk bvb hfx\nbvb: xhk hfx\npzl: lsr hfx nvd\nqnr: nvd\nntq: jqt hfx bvb xhk\nnvd: lhk\nlsr: lhk\nrzs: qnr cmg lsr rsh\nfrs: qnr lhk lsr";
+
+    #[test]
+    fn test_stoer_wagner() {
+        let (graph, _) = parse_adjacency_list(EXAMPLE_2023_25).unwrap();
+        let cut = graph.stoer_wagner(|_| 1).unwrap();
+        assert_eq!(3, cut.weight);
+        assert_eq!(3, cut.edges.len());
+        assert_eq!(54, cut.partition.0.len() * cut.partition.1.len());
+    }
+
+    #[test]
+    fn test_karger() {
+        let (graph, _) = parse_adjacency_list(EXAMPLE_2023_25).unwrap();
+        let cut = graph.karger(|_| 1, 50, 2023).unwrap();
+        assert_eq!(3, cut.weight);
+        assert_eq!(54, cut.partition.0.len() * cut.partition.1.len());
+    }
+
+    #[test]
+    fn test_weighted() {
+        let mut graph: UndirectedGraph<(), u64> = UndirectedGraph::new();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        graph.add_edge(0, 1, 10).unwrap();
+        graph.add_edge(1, 2, 2).unwrap();
+        graph.add_edge(2, 3, 10).unwrap();
+        graph.add_edge(3, 0, 3).unwrap();
+        let cut = graph.stoer_wagner(|&w| w).unwrap();
+        assert_eq!(5, cut.weight);
+        assert_eq!((vec![0, 1], vec![2, 3]), cut.partition);
+        assert_eq!(vec![1, 3], cut.edges);
+    }
+}
@@ -1,13 +1,31 @@
+pub mod components;
+pub mod dense;
+pub mod directed;
+pub mod flow;
+pub mod grid;
+pub mod longest_path;
+pub mod min_cut;
+pub mod parse;
+pub mod scc;
+pub mod traversal;
+
 #[derive(Debug)]
 pub enum GraphError {
     NodeIndexOutOfBounds(NodeIndex),
     EdgeIndexOutOfBounds(EdgeIndex),
+    NoCycle(NodeIndex),
+    TooFewNodes(usize),
+    Cycle(Vec<NodeIndex>),
+    SourceIsSink(NodeIndex),
+    NotBipartite(EdgeIndex),
 }
 
 pub type NodeIndex = usize;
 
 #[derive(Debug)]
 struct Node<N> {
+    // only shows up in the `Debug` output, which derived impls don't count as a read
+    #[allow(dead_code)]
     id: NodeIndex,
     inner: N,
 }
@@ -28,6 +46,12 @@ pub struct UndirectedGraph<N, E> {
     edges: Vec<Edge<E>>,
 }
 
+impl<N, E> Default for UndirectedGraph<N, E> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
 impl<N, E> UndirectedGraph<N, E> {
     pub fn new() -> Self {
         return Self {
@@ -64,6 +88,13 @@ impl<N, E> UndirectedGraph<N, E> {
         };
     }
 
+    pub fn edge_endpoints(&self, edge: EdgeIndex) -> Result<(NodeIndex, NodeIndex), GraphError> {
+        return match self.edges.get(edge) {
+            Some(e) => Ok((e.a, e.b)),
+            None => Err(GraphError::EdgeIndexOutOfBounds(edge)),
+        };
+    }
+
     pub fn node_count(&self) -> usize {
         return self.nodes.len();
     }
@@ -122,6 +153,18 @@ impl<N, E> UndirectedGraph<N, E> {
             current: 0,
         });
     }
+
+    // (neighbor, edge) pairs for every node, so algorithms don't rescan all edges per step
+    fn adjacency(&self) -> Vec<Vec<(NodeIndex, EdgeIndex)>> {
+        let mut adj = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            adj[edge.a].push((edge.b, edge.id));
+            if edge.a != edge.b {
+                adj[edge.b].push((edge.a, edge.id));
+            }
+        }
+        return adj;
+    }
 }
 
 pub struct Neighbors<'graph, N, E> {
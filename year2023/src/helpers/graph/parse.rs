@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use super::{directed::DirectedGraph, NodeIndex, UndirectedGraph};
+
+/// What went wrong, with the (1-based) number of the offending line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingSeparator(usize, &'static str),
+    EmptyLabel(usize),
+}
+
+/// Interned node labels, mapping names to nodes and back.
+#[derive(Debug, Clone, Default)]
+pub struct Labels {
+    names: Vec<String>,
+    nodes: HashMap<String, NodeIndex>,
+}
+
+impl Labels {
+    pub fn len(&self) -> usize {
+        return self.names.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.names.is_empty();
+    }
+
+    pub fn node(&self, name: &str) -> Option<NodeIndex> {
+        return self.nodes.get(name).copied();
+    }
+
+    pub fn name(&self, node: NodeIndex) -> Option<&str> {
+        return self.names.get(node).map(String::as_str);
+    }
+
+    // labels are interned in the same order as the nodes get added
+    fn intern(&mut self, name: &str, line: usize) -> Result<(NodeIndex, bool), ParseError> {
+        if name.is_empty() {
+            return Err(ParseError::EmptyLabel(line));
+        }
+        if let Some(&node) = self.nodes.get(name) {
+            return Ok((node, false));
+        }
+        let node = self.names.len();
+        self.names.push(name.to_owned());
+        self.nodes.insert(name.to_owned(), node);
+        return Ok((node, true));
+    }
+}
+
+fn split<'a>(
+    line: &'a str,
+    separator: &'static str,
+    number: usize,
+) -> Result<(&'a str, &'a str), ParseError> {
+    let (left, right) = line
+        .split_once(separator)
+        .ok_or(ParseError::MissingSeparator(number, separator))?;
+    return Ok((left.trim(), right.trim()));
+}
+
+fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    return input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+}
+
+/// Parses `AAA = (BBB, CCC)` lines into edges from `AAA` to each listed node, every edge
+/// holding its position in the tuple.
+pub fn parse_tuples(input: &str) -> Result<(DirectedGraph<(), usize>, Labels), ParseError> {
+    let mut graph = DirectedGraph::new();
+    let mut labels = Labels::default();
+    let mut intern = |graph: &mut DirectedGraph<(), usize>, name: &str, line: usize| {
+        let (node, new) = labels.intern(name, line)?;
+        if new {
+            graph.add_node(());
+        }
+        return Ok(node);
+    };
+
+    for (number, line) in numbered_lines(input) {
+        let (key, tuple) = split(line, "=", number)?;
+        let tuple = tuple
+            .strip_prefix('(')
+            .ok_or(ParseError::MissingSeparator(number, "("))?
+            .strip_suffix(')')
+            .ok_or(ParseError::MissingSeparator(number, ")"))?;
+        let from = intern(&mut graph, key, number)?;
+        for (position, name) in tuple.split(',').enumerate() {
+            let to = intern(&mut graph, name.trim(), number)?;
+            graph.add_edge(from, to, position).unwrap();
+        }
+    }
+    return Ok((graph, labels));
+}
+
+/// Parses `a: b c d` lines into undirected edges between `a` and each listed node.
+pub fn parse_adjacency_list(input: &str) -> Result<(UndirectedGraph<(), ()>, Labels), ParseError> {
+    let mut graph = UndirectedGraph::new();
+    let mut labels = Labels::default();
+    let mut intern = |graph: &mut UndirectedGraph<(), ()>, name: &str, line: usize| {
+        let (node, new) = labels.intern(name, line)?;
+        if new {
+            graph.add_node(());
+        }
+        return Ok(node);
+    };
+
+    for (number, line) in numbered_lines(input) {
+        let (key, list) = split(line, ":", number)?;
+        let from = intern(&mut graph, key, number)?;
+        for name in list.split_ascii_whitespace() {
+            let to = intern(&mut graph, name, number)?;
+            graph.add_edge(from, to, ()).unwrap();
+        }
+    }
+    return Ok((graph, labels));
+}
+
+/// Parses `%a -> b, c` lines into edges from `a` to each listed node. A leading symbol like
+/// `%` or `&` is not part of the label but kept as the node's data.
+pub fn parse_wiring(input: &str) -> Result<(DirectedGraph<Option<char>, ()>, Labels), ParseError> {
+    let mut graph = DirectedGraph::new();
+    let mut labels = Labels::default();
+    let mut intern = |graph: &mut DirectedGraph<Option<char>, ()>, name: &str, line: usize| {
+        let (node, new) = labels.intern(name, line)?;
+        if new {
+            graph.add_node(None);
+        }
+        return Ok(node);
+    };
+
+    for (number, line) in numbered_lines(input) {
+        let (module, targets) = split(line, "->", number)?;
+        let (kind, name) = match module.chars().next() {
+            Some(c) if !c.is_alphanumeric() => (Some(c), &module[c.len_utf8()..]),
+            _ => (None, module),
+        };
+        let from = intern(&mut graph, name, number)?;
+        *graph.get_mut_node(from).unwrap() = kind;
+        for target in targets.split(',') {
+            let to = intern(&mut graph, target.trim(), number)?;
+            graph.add_edge(from, to, ()).unwrap();
+        }
+    }
+    return Ok((graph, labels));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tuples() {
+        let input = "AAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+        let (graph, labels) = parse_tuples(input).unwrap();
+        assert_eq!(3, labels.len());
+        assert_eq!(6, graph.edge_count());
+        assert_eq!(Some("ZZZ"), labels.name(2));
+        let bbb = labels.node("BBB").unwrap();
+        assert_eq!(
+            vec![0, 2],
+            graph.successors(bbb).unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(1, *graph.get_edge(3).unwrap());
+
+        assert_eq!(
+            Err(ParseError::MissingSeparator(2, "=")),
+            parse_tuples("A = (B, C)\nB (A, C)").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::MissingSeparator(1, ")")),
+            parse_tuples("A = (B, C").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::EmptyLabel(1)),
+            parse_tuples("A = (B, )").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_parse_adjacency_list() {
+        let input = "jqt: rhn xhk nvd\nrsh: frs pzl lsr\nxhk: hfx";
+        let (graph, labels) = parse_adjacency_list(input).unwrap();
+        assert_eq!(9, graph.node_count());
+        assert_eq!(7, graph.edge_count());
+        assert_eq!(2, graph.degree(labels.node("xhk").unwrap()).unwrap());
+        assert_eq!(
+            Err(ParseError::MissingSeparator(1, ":")),
+            parse_adjacency_list("a b c").map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_parse_wiring() {
+        let input = "broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a";
+        let (graph, labels) = parse_wiring(input).unwrap();
+        assert_eq!(5, labels.len());
+        assert_eq!(
+            Some('&'),
+            *graph.get_node(labels.node("inv").unwrap()).unwrap()
+        );
+        assert_eq!(
+            None,
+            *graph.get_node(labels.node("broadcaster").unwrap()).unwrap()
+        );
+        assert_eq!(7, graph.edge_count());
+        assert_eq!(
+            Err(ParseError::EmptyLabel(1)),
+            parse_wiring("% -> a").map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::{directed::DirectedGraph, GraphError, NodeIndex};
+
+impl<N, E> DirectedGraph<N, E> {
+    /// Strongly connected components (Tarjan), in topological order: edges between
+    /// components only ever point to a later component.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<NodeIndex>> {
+        let adj = self.out_adjacency();
+        let n = self.nodes.len();
+
+        let mut index: Vec<Option<usize>> = vec![None; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack: Vec<NodeIndex> = Vec::new();
+        let mut next_index = 0;
+        let mut components: Vec<Vec<NodeIndex>> = Vec::new();
+
+        for root in 0..n {
+            if index[root].is_some() {
+                continue;
+            }
+            // (node, position in its adjacency list), instead of recursing
+            let mut calls: Vec<(NodeIndex, usize)> = vec![(root, 0)];
+            index[root] = Some(next_index);
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (node, ref mut pos)) = calls.last_mut() {
+                if let Some(&(next, _)) = adj[node].get(*pos) {
+                    *pos += 1;
+                    match index[next] {
+                        None => {
+                            index[next] = Some(next_index);
+                            low[next] = next_index;
+                            next_index += 1;
+                            stack.push(next);
+                            on_stack[next] = true;
+                            calls.push((next, 0));
+                        }
+                        Some(i) if on_stack[next] => low[node] = low[node].min(i),
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+                if Some(low[node]) == index[node] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+
+        components.reverse();
+        return components;
+    }
+
+    /// Collapses every strongly connected component into one node holding its members.
+    /// Also returns the component of every node of `self`.
+    pub fn condensation(&self) -> (DirectedGraph<Vec<NodeIndex>, ()>, Vec<NodeIndex>) {
+        let components = self.strongly_connected_components();
+        let mut component_of = vec![0; self.nodes.len()];
+        for (i, component) in components.iter().enumerate() {
+            for &node in component {
+                component_of[node] = i;
+            }
+        }
+
+        let mut condensed = DirectedGraph::new();
+        for component in components {
+            condensed.add_node(component);
+        }
+        let mut seen: HashSet<(NodeIndex, NodeIndex)> = HashSet::new();
+        for edge in &self.edges {
+            let (a, b) = (component_of[edge.a], component_of[edge.b]);
+            if a != b && seen.insert((a, b)) {
+                condensed.add_edge(a, b, ()).unwrap();
+            }
+        }
+        return (condensed, component_of);
+    }
+
+    /// Topological order of all nodes (Kahn), or one cycle in walking order.
+    pub fn topological_order(&self) -> Result<Vec<NodeIndex>, GraphError> {
+        let adj = self.out_adjacency();
+        let mut in_degree = vec![0; self.nodes.len()];
+        for edge in &self.edges {
+            in_degree[edge.b] += 1;
+        }
+
+        let mut queue: VecDeque<NodeIndex> = (0..self.nodes.len())
+            .filter(|&n| in_degree[n] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &(next, _) in &adj[node] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+        if order.len() == self.nodes.len() {
+            return Ok(order);
+        }
+
+        // every leftover node still has a leftover predecessor, so walking backwards
+        // has to run into a node twice
+        let mut predecessor = vec![None; self.nodes.len()];
+        for edge in &self.edges {
+            if in_degree[edge.a] > 0 && in_degree[edge.b] > 0 {
+                predecessor[edge.b] = Some(edge.a);
+            }
+        }
+        let mut walked = vec![false; self.nodes.len()];
+        let mut node = (0..self.nodes.len()).find(|&n| in_degree[n] > 0).unwrap();
+        while !walked[node] {
+            walked[node] = true;
+            node = predecessor[node].unwrap();
+        }
+        let mut cycle = vec![node];
+        let mut current = predecessor[node].unwrap();
+        while current != node {
+            cycle.push(current);
+            current = predecessor[current].unwrap();
+        }
+        cycle.reverse();
+        cycle.rotate_right(1);
+        return Err(GraphError::Cycle(cycle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(n: usize, edges: &[(NodeIndex, NodeIndex)]) -> DirectedGraph<(), ()> {
+        let mut graph = DirectedGraph::new();
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(a, b) in edges {
+            graph.add_edge(a, b, ()).unwrap();
+        }
+        return graph;
+    }
+
+    #[test]
+    fn test_scc() {
+        let g = graph(6, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (5, 4)]);
+        assert_eq!(
+            vec![vec![5], vec![0, 1, 2], vec![3, 4]],
+            g.strongly_connected_components()
+        );
+
+        let (condensed, component_of) = g.condensation();
+        assert_eq!(3, condensed.node_count());
+        assert_eq!(2, condensed.edge_count());
+        assert_eq!(vec![1, 1, 1, 2, 2, 0], component_of);
+        assert!(condensed.topological_order().is_ok());
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let g = graph(4, &[(3, 1), (1, 0), (3, 2), (2, 0)]);
+        assert_eq!(vec![3, 1, 2, 0], g.topological_order().unwrap());
+
+        let g = graph(4, &[(0, 1), (1, 2), (2, 3), (3, 1)]);
+        match g.topological_order() {
+            Err(GraphError::Cycle(cycle)) => assert_eq!(vec![1, 2, 3], cycle),
+            other => panic!("expected a cycle, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::VecDeque;
+
+use super::{directed::DirectedGraph, EdgeIndex, GraphError, NodeIndex, UndirectedGraph};
+
+/// Breadth first iterator yielding `(node, depth, parent)` for every reached node.
+pub struct Bfs {
+    adj: Vec<Vec<(NodeIndex, EdgeIndex)>>,
+    visited: Vec<bool>,
+    queue: VecDeque<(NodeIndex, usize, Option<NodeIndex>)>,
+}
+
+impl Bfs {
+    /// Searches from all of `starts` at once, never entering a node marked in `visited`.
+    fn new(
+        adj: Vec<Vec<(NodeIndex, EdgeIndex)>>,
+        starts: &[NodeIndex],
+        mut visited: Vec<bool>,
+    ) -> Self {
+        visited.resize(adj.len(), false);
+        let mut queue = VecDeque::new();
+        for &start in starts {
+            if !visited[start] {
+                visited[start] = true;
+                queue.push_back((start, 0, None));
+            }
+        }
+        return Self {
+            adj,
+            visited,
+            queue,
+        };
+    }
+
+    /// Treats `node` as already seen, so the search never enters it.
+    pub fn mark_visited(&mut self, node: NodeIndex) {
+        self.visited[node] = true;
+    }
+
+    pub fn into_visited(self) -> Vec<bool> {
+        return self.visited;
+    }
+}
+
+impl Iterator for Bfs {
+    type Item = (NodeIndex, usize, Option<NodeIndex>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth, parent) = self.queue.pop_front()?;
+        for &(next, _) in &self.adj[node] {
+            if !self.visited[next] {
+                self.visited[next] = true;
+                self.queue.push_back((next, depth + 1, Some(node)));
+            }
+        }
+        return Some((node, depth, parent));
+    }
+}
+
+/// Depth first (preorder) iterator yielding `(node, depth, parent)` for every reached node.
+pub struct Dfs {
+    adj: Vec<Vec<(NodeIndex, EdgeIndex)>>,
+    visited: Vec<bool>,
+    stack: Vec<(NodeIndex, usize, Option<NodeIndex>)>,
+}
+
+impl Dfs {
+    /// Searches from each of `starts` in turn, never entering a node marked in `visited`.
+    fn new(
+        adj: Vec<Vec<(NodeIndex, EdgeIndex)>>,
+        starts: &[NodeIndex],
+        mut visited: Vec<bool>,
+    ) -> Self {
+        visited.resize(adj.len(), false);
+        return Self {
+            visited,
+            adj,
+            stack: starts.iter().rev().map(|&start| (start, 0, None)).collect(),
+        };
+    }
+
+    /// Treats `node` as already seen, so the search never enters it.
+    pub fn mark_visited(&mut self, node: NodeIndex) {
+        self.visited[node] = true;
+    }
+
+    pub fn into_visited(self) -> Vec<bool> {
+        return self.visited;
+    }
+}
+
+impl Iterator for Dfs {
+    type Item = (NodeIndex, usize, Option<NodeIndex>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, depth, parent)) = self.stack.pop() {
+            if self.visited[node] {
+                continue;
+            }
+            self.visited[node] = true;
+            for &(next, _) in self.adj[node].iter().rev() {
+                if !self.visited[next] {
+                    self.stack.push((next, depth + 1, Some(node)));
+                }
+            }
+            return Some((node, depth, parent));
+        }
+        return None;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    Tree,
+    /// In undirected graphs every edge that isn't a tree edge.
+    Back,
+    /// Only in directed graphs: to an already finished descendant.
+    Forward,
+    /// Only in directed graphs: to an already finished node in another branch.
+    Cross,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// Returned from `discover`: don't descend into this node.
+    Prune,
+    Stop,
+}
+
+pub trait Visitor {
+    fn discover(&mut self, _node: NodeIndex, _depth: usize) -> Control {
+        return Control::Continue;
+    }
+
+    fn finish(&mut self, _node: NodeIndex) {}
+
+    fn edge(
+        &mut self,
+        _from: NodeIndex,
+        _to: NodeIndex,
+        _edge: EdgeIndex,
+        _kind: EdgeKind,
+    ) -> Control {
+        return Control::Continue;
+    }
+}
+
+// shared by both graph kinds, an undirected edge is listed at both of its ends
+fn depth_first_visit<V: Visitor>(
+    adj: &[Vec<(NodeIndex, EdgeIndex)>],
+    directed: bool,
+    start: NodeIndex,
+    visited: &mut Vec<bool>,
+    visitor: &mut V,
+) -> Control {
+    visited.resize(adj.len(), false);
+    if visited[start] {
+        return Control::Continue;
+    }
+
+    let mut discovered: Vec<Option<usize>> = vec![None; adj.len()];
+    let mut finished = vec![false; adj.len()];
+    let edge_count = adj.iter().flatten().map(|&(_, e)| e + 1).max().unwrap_or(0);
+    let mut seen_edge = vec![false; edge_count];
+    let mut time = 0;
+
+    // (node, position in its adjacency list)
+    let mut stack: Vec<(NodeIndex, usize)> = Vec::new();
+    visited[start] = true;
+    discovered[start] = Some(time);
+    time += 1;
+    match visitor.discover(start, 0) {
+        Control::Stop => return Control::Stop,
+        Control::Prune => {
+            finished[start] = true;
+            visitor.finish(start);
+            return Control::Continue;
+        }
+        Control::Continue => stack.push((start, 0)),
+    }
+
+    while let Some(&mut (node, ref mut pos)) = stack.last_mut() {
+        let Some(&(next, edge)) = adj[node].get(*pos) else {
+            stack.pop();
+            finished[node] = true;
+            visitor.finish(node);
+            continue;
+        };
+        *pos += 1;
+        if !directed {
+            if seen_edge[edge] {
+                continue;
+            }
+            seen_edge[edge] = true;
+        }
+
+        let kind = match (discovered[next], finished[next]) {
+            (None, _) if !visited[next] => EdgeKind::Tree,
+            // pre-seeded nodes are never entered
+            (None, _) => continue,
+            // even a pruned node, finished early, can only be reached back along the tree
+            (Some(_), _) if !directed => EdgeKind::Back,
+            (Some(_), false) => EdgeKind::Back,
+            (Some(t), true) if t > discovered[node].unwrap() => EdgeKind::Forward,
+            (Some(_), true) => EdgeKind::Cross,
+        };
+        if visitor.edge(node, next, edge, kind) == Control::Stop {
+            return Control::Stop;
+        }
+        if kind != EdgeKind::Tree {
+            continue;
+        }
+
+        visited[next] = true;
+        discovered[next] = Some(time);
+        time += 1;
+        match visitor.discover(next, stack.len()) {
+            Control::Stop => return Control::Stop,
+            Control::Prune => {
+                finished[next] = true;
+                visitor.finish(next);
+            }
+            Control::Continue => stack.push((next, 0)),
+        }
+    }
+    return Control::Continue;
+}
+
+impl<N, E> UndirectedGraph<N, E> {
+    pub fn bfs(&self, start: NodeIndex) -> Result<Bfs, GraphError> {
+        return self.bfs_seeded(&[start], Vec::new());
+    }
+
+    /// Breadth first search from all of `starts`, skipping every node already marked in
+    /// `visited` (which may be empty).
+    pub fn bfs_seeded(&self, starts: &[NodeIndex], visited: Vec<bool>) -> Result<Bfs, GraphError> {
+        for &start in starts {
+            self.get_node(start)?;
+        }
+        return Ok(Bfs::new(self.adjacency(), starts, visited));
+    }
+
+    pub fn dfs(&self, start: NodeIndex) -> Result<Dfs, GraphError> {
+        return self.dfs_seeded(&[start], Vec::new());
+    }
+
+    /// Depth first search from each of `starts` in turn, skipping every node already
+    /// marked in `visited` (which may be empty).
+    pub fn dfs_seeded(&self, starts: &[NodeIndex], visited: Vec<bool>) -> Result<Dfs, GraphError> {
+        for &start in starts {
+            self.get_node(start)?;
+        }
+        return Ok(Dfs::new(self.adjacency(), starts, visited));
+    }
+
+    /// Depth first search from `start` reporting to `visitor`, skipping every node
+    /// already marked in `visited` (which may be empty). Returns `Control::Stop` if the
+    /// visitor stopped the search.
+    pub fn depth_first_visit<V: Visitor>(
+        &self,
+        start: NodeIndex,
+        visited: &mut Vec<bool>,
+        visitor: &mut V,
+    ) -> Result<Control, GraphError> {
+        self.get_node(start)?;
+        return Ok(depth_first_visit(
+            &self.adjacency(),
+            false,
+            start,
+            visited,
+            visitor,
+        ));
+    }
+}
+
+impl<N, E> DirectedGraph<N, E> {
+    pub fn bfs(&self, start: NodeIndex) -> Result<Bfs, GraphError> {
+        return self.bfs_seeded(&[start], Vec::new());
+    }
+
+    /// Breadth first search from all of `starts`, skipping every node already marked in
+    /// `visited` (which may be empty).
+    pub fn bfs_seeded(&self, starts: &[NodeIndex], visited: Vec<bool>) -> Result<Bfs, GraphError> {
+        for &start in starts {
+            self.get_node(start)?;
+        }
+        return Ok(Bfs::new(self.out_adjacency(), starts, visited));
+    }
+
+    pub fn dfs(&self, start: NodeIndex) -> Result<Dfs, GraphError> {
+        return self.dfs_seeded(&[start], Vec::new());
+    }
+
+    /// Depth first search from each of `starts` in turn, skipping every node already
+    /// marked in `visited` (which may be empty).
+    pub fn dfs_seeded(&self, starts: &[NodeIndex], visited: Vec<bool>) -> Result<Dfs, GraphError> {
+        for &start in starts {
+            self.get_node(start)?;
+        }
+        return Ok(Dfs::new(self.out_adjacency(), starts, visited));
+    }
+
+    /// Depth first search from `start` reporting to `visitor`, skipping every node
+    /// already marked in `visited` (which may be empty). Returns `Control::Stop` if the
+    /// visitor stopped the search.
+    pub fn depth_first_visit<V: Visitor>(
+        &self,
+        start: NodeIndex,
+        visited: &mut Vec<bool>,
+        visitor: &mut V,
+    ) -> Result<Control, GraphError> {
+        self.get_node(start)?;
+        return Ok(depth_first_visit(
+            &self.out_adjacency(),
+            true,
+            start,
+            visited,
+            visitor,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        stop_at: Option<NodeIndex>,
+        prune_at: Option<NodeIndex>,
+    }
+
+    impl Visitor for Recorder {
+        fn discover(&mut self, node: NodeIndex, depth: usize) -> Control {
+            self.events.push(format!("d{}@{}", node, depth));
+            if self.stop_at == Some(node) {
+                return Control::Stop;
+            }
+            if self.prune_at == Some(node) {
+                return Control::Prune;
+            }
+            return Control::Continue;
+        }
+
+        fn finish(&mut self, node: NodeIndex) {
+            self.events.push(format!("f{}", node));
+        }
+
+        fn edge(
+            &mut self,
+            from: NodeIndex,
+            to: NodeIndex,
+            _: EdgeIndex,
+            kind: EdgeKind,
+        ) -> Control {
+            if kind != EdgeKind::Tree {
+                self.events.push(format!("{:?}{}-{}", kind, from, to));
+            }
+            return Control::Continue;
+        }
+    }
+
+    fn square() -> UndirectedGraph<(), ()> {
+        let mut graph = UndirectedGraph::new();
+        for _ in 0..5 {
+            graph.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0), (3, 4)] {
+            graph.add_edge(a, b, ()).unwrap();
+        }
+        return graph;
+    }
+
+    #[test]
+    fn test_bfs_dfs() {
+        let graph = square();
+        let bfs: Vec<_> = graph.bfs(0).unwrap().collect();
+        assert_eq!(
+            vec![
+                (0, 0, None),
+                (1, 1, Some(0)),
+                (3, 1, Some(0)),
+                (2, 2, Some(1)),
+                (4, 2, Some(3))
+            ],
+            bfs
+        );
+        let dfs: Vec<_> = graph.dfs(0).unwrap().map(|(n, d, _)| (n, d)).collect();
+        assert_eq!(vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)], dfs);
+
+        let mut seeded = graph.bfs(0).unwrap();
+        seeded.mark_visited(1);
+        assert_eq!(4, seeded.count());
+        assert_eq!(
+            Some(2),
+            graph.bfs(0).unwrap().find(|&(n, _, _)| n == 4).map(|x| x.1)
+        );
+    }
+
+    #[test]
+    fn test_seeded() {
+        let graph = square();
+        let visited = vec![false, true];
+        let bfs: Vec<_> = graph.bfs_seeded(&[0, 4], visited).unwrap().collect();
+        assert_eq!(
+            vec![(0, 0, None), (4, 0, None), (3, 1, Some(0)), (2, 2, Some(3))],
+            bfs
+        );
+        let dfs: Vec<_> = graph.dfs_seeded(&[2, 4], Vec::new()).unwrap().collect();
+        assert_eq!(
+            vec![2, 1, 0, 3, 4],
+            dfs.iter().map(|&(n, _, _)| n).collect::<Vec<_>>()
+        );
+        let visited = vec![false, false, false, true, false];
+        let dfs: Vec<_> = graph.dfs_seeded(&[4, 3], visited).unwrap().collect();
+        assert_eq!(vec![(4, 0, None)], dfs);
+        assert!(graph.bfs_seeded(&[0, 5], Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_visitor_undirected() {
+        let graph = square();
+        let mut recorder = Recorder::default();
+        let control = graph
+            .depth_first_visit(0, &mut Vec::new(), &mut recorder)
+            .unwrap();
+        assert_eq!(Control::Continue, control);
+        assert_eq!(
+            "d0@0 d1@1 d2@2 d3@3 Back3-0 d4@4 f4 f3 f2 f1 f0",
+            recorder.events.join(" ")
+        );
+
+        let mut recorder = Recorder {
+            stop_at: Some(2),
+            ..Default::default()
+        };
+        let mut visited = vec![false, false, false, true, false];
+        let control = graph
+            .depth_first_visit(0, &mut visited, &mut recorder)
+            .unwrap();
+        assert_eq!(Control::Stop, control);
+        assert_eq!("d0@0 d1@1 d2@2", recorder.events.join(" "));
+
+        // the edge from 2 into the pruned 1 is neither forward nor cross
+        let mut recorder = Recorder {
+            prune_at: Some(1),
+            ..Default::default()
+        };
+        graph
+            .depth_first_visit(0, &mut Vec::new(), &mut recorder)
+            .unwrap();
+        assert_eq!(
+            "d0@0 d1@1 f1 d3@1 d2@2 Back2-1 f2 d4@2 f4 f3 f0",
+            recorder.events.join(" ")
+        );
+    }
+
+    #[test]
+    fn test_visitor_directed() {
+        let mut graph: DirectedGraph<(), ()> = DirectedGraph::new();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (0, 2), (2, 0), (0, 3), (3, 2)] {
+            graph.add_edge(a, b, ()).unwrap();
+        }
+        let mut recorder = Recorder::default();
+        graph
+            .depth_first_visit(0, &mut Vec::new(), &mut recorder)
+            .unwrap();
+        assert_eq!(
+            "d0@0 d1@1 d2@2 Back2-0 f2 f1 Forward0-2 d3@1 Cross3-2 f3 f0",
+            recorder.events.join(" ")
+        );
+    }
+}
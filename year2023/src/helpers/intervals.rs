@@ -0,0 +1,203 @@
+use std::ops::Range;
+
+use super::math::Integer;
+
+/// A set of values stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        return Self { ranges: Vec::new() };
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        return &self.ranges;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.ranges.is_empty();
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        return self.ranges.get(i).is_some_and(|r| r.start <= value);
+    }
+
+    /// Adds `range`, merging it with every range it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        if first == last {
+            self.ranges.insert(first, range);
+            return;
+        }
+        let start = range.start.min(self.ranges[first].start);
+        let end = range.end.max(self.ranges[last - 1].end);
+        self.ranges.splice(first..last, [start..end]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in &other.ranges {
+            result.insert(range.clone());
+        }
+        return result;
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        return Self { ranges };
+    }
+
+    /// Everything in `self` that is not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                if start < other.ranges[k].start {
+                    ranges.push(start..other.ranges[k].start);
+                }
+                start = start.max(other.ranges[k].end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        return Self { ranges };
+    }
+
+    /// The values below `at` and the ones from `at` on.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for range in &self.ranges {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+        return (Self { ranges: below }, Self { ranges: above });
+    }
+
+    /// The ranges cut at every one of `boundaries`, so no piece straddles a boundary.
+    pub fn split_by(&self, boundaries: &[T]) -> Vec<Range<T>> {
+        let mut boundaries = boundaries.to_vec();
+        boundaries.sort_unstable();
+        let mut pieces = Vec::new();
+        for range in &self.ranges {
+            let mut start = range.start;
+            let first = boundaries.partition_point(|&b| b <= range.start);
+            for &boundary in boundaries[first..].iter().take_while(|&&b| b < range.end) {
+                if boundary > start {
+                    pieces.push(start..boundary);
+                    start = boundary;
+                }
+            }
+            pieces.push(start..range.end);
+        }
+        return pieces;
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    /// The number of values in the set.
+    pub fn len(&self) -> T {
+        return self
+            .ranges
+            .iter()
+            .fold(T::ZERO, |acc, r| acc + (r.end - r.start));
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        return set;
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        return Self::from_iter([range]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let set: IntervalSet<i64> = [5..8, 0..2, 2..3, 10..10, 7..9].into_iter().collect();
+        assert_eq!(&[0..3, 5..9], set.ranges());
+        assert_eq!(7, set.len());
+        assert!(set.contains(2));
+        assert!(!set.contains(3));
+        assert!(!set.contains(9));
+
+        let mut set = set;
+        set.insert(-4..20);
+        assert_eq!(IntervalSet::from(-4..20), set);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: IntervalSet<u32> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<u32> = [5..25, 28..40].into_iter().collect();
+        assert_eq!(IntervalSet::from(0..40), a.union(&b));
+        assert_eq!(&[5..10, 20..25, 28..30], a.intersection(&b).ranges());
+        assert_eq!(&[0..5, 25..28], a.difference(&b).ranges());
+        assert_eq!(&[10..20, 30..40], b.difference(&a).ranges());
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a, a.difference(&IntervalSet::new()));
+    }
+
+    #[test]
+    fn test_split() {
+        let set: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+        let (below, above) = set.split_at(25);
+        assert_eq!(&[0..10, 20..25], below.ranges());
+        assert_eq!(IntervalSet::from(25..30), above);
+        assert_eq!(vec![0..4, 4..10, 20..30], set.split_by(&[15, 4, 0, 30, 4]));
+    }
+}
@@ -0,0 +1,238 @@
+use super::{math::MathError, rational::Ratio};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearError {
+    /// The equations contradict each other.
+    Inconsistent,
+    /// Solvable, but this many unknowns are left free.
+    Underdetermined(usize),
+    /// The rows don't all have one coefficient per unknown.
+    Shape,
+    Math(MathError),
+}
+
+impl From<MathError> for LinearError {
+    fn from(error: MathError) -> Self {
+        return Self::Math(error);
+    }
+}
+
+/// Solves `matrix · x = rhs` exactly with Gauss-Jordan elimination. There may be more
+/// equations than unknowns as long as they agree.
+pub fn solve(matrix: &[Vec<Ratio>], rhs: &[Ratio]) -> Result<Vec<Ratio>, LinearError> {
+    let unknowns = matrix.first().map_or(0, Vec::len);
+    if matrix.len() != rhs.len() || matrix.iter().any(|row| row.len() != unknowns) {
+        return Err(LinearError::Shape);
+    }
+    // augmented rows, the right hand side in the last column
+    let mut rows: Vec<Vec<Ratio>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| row.iter().copied().chain([b]).collect())
+        .collect();
+
+    let mut rank = 0;
+    for col in 0..unknowns {
+        // the smallest pivot keeps the fractions from growing faster than they have to
+        let Some(pivot) = (rank..rows.len())
+            .filter(|&r| rows[r][col] != Ratio::ZERO)
+            .min_by_key(|&r| rows[r][col].numer().unsigned_abs())
+        else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+
+        let inverse = rows[rank][col].checked_recip()?;
+        for value in &mut rows[rank][col..] {
+            *value = value.checked_mul(&inverse)?;
+        }
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if r == rank || factor == Ratio::ZERO {
+                continue;
+            }
+            for (value, pivot) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *value = value.checked_sub(&factor.checked_mul(pivot)?)?;
+            }
+        }
+        rank += 1;
+    }
+
+    if rows[rank..].iter().any(|row| row[unknowns] != Ratio::ZERO) {
+        return Err(LinearError::Inconsistent);
+    }
+    if rank < unknowns {
+        return Err(LinearError::Underdetermined(unknowns - rank));
+    }
+    // reduced row echelon form with full rank, row i is x_i = rhs_i
+    return Ok(rows[..unknowns].iter().map(|row| row[unknowns]).collect());
+}
+
+/// Like `solve` for integer coefficients.
+pub fn solve_integer(matrix: &[Vec<i128>], rhs: &[i128]) -> Result<Vec<Ratio>, LinearError> {
+    let matrix: Vec<Vec<Ratio>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|&x| Ratio::from(x)).collect())
+        .collect();
+    let rhs: Vec<Ratio> = rhs.iter().map(|&x| Ratio::from(x)).collect();
+    return solve(&matrix, &rhs);
+}
+
+/// Something moving in a straight line, at `position + t * velocity` at time `t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trajectory {
+    pub position: [i128; 3],
+    pub velocity: [i128; 3],
+}
+
+fn sub(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    return [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+}
+
+fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    return [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ];
+}
+
+/// Three linear equations in the unknowns `(P, V)` = `[px, py, pz, vx, vy, vz]` of a
+/// trajectory that hits both `a` and `b`, each at some time.
+///
+/// Hitting `a` means `(P - pa) × (V - va) = 0`. Subtracting the same for `b` cancels the
+/// quadratic `P × V`, leaving `P × (vb - va) + (pb - pa) × V = pb × vb - pa × va`.
+pub fn intercept_equations(a: &Trajectory, b: &Trajectory) -> Vec<([i128; 6], i128)> {
+    let [wx, wy, wz] = sub(b.velocity, a.velocity);
+    let [qx, qy, qz] = sub(b.position, a.position);
+    let rhs = sub(cross(b.position, b.velocity), cross(a.position, a.velocity));
+    return vec![
+        ([0, wz, -wy, 0, -qz, qy], rhs[0]),
+        ([-wz, 0, wx, qz, 0, -qx], rhs[1]),
+        ([wy, -wx, 0, -qy, qx, 0], rhs[2]),
+    ];
+}
+
+/// The trajectory hitting every one of `trajectories` as `[px, py, pz, vx, vy, vz]`,
+/// worked out from the first three of them.
+pub fn intercepting_trajectory(trajectories: &[Trajectory]) -> Result<[Ratio; 6], LinearError> {
+    let Some(first) = trajectories.first() else {
+        return Err(LinearError::Underdetermined(6));
+    };
+    // moving everything relative to the first one keeps the numbers small
+    let origin = first.position;
+    let shift = |t: &Trajectory| Trajectory {
+        position: sub(t.position, origin),
+        velocity: t.velocity,
+    };
+
+    let mut matrix = Vec::new();
+    let mut rhs = Vec::new();
+    for other in trajectories.iter().skip(1).take(2) {
+        for (row, b) in intercept_equations(&shift(first), &shift(other)) {
+            matrix.push(row.to_vec());
+            rhs.push(b);
+        }
+    }
+    if matrix.is_empty() {
+        return Err(LinearError::Underdetermined(6));
+    }
+
+    let solution = solve_integer(&matrix, &rhs)?;
+    let mut result = [Ratio::ZERO; 6];
+    result.copy_from_slice(&solution);
+    for (value, &offset) in result[..3].iter_mut().zip(&origin) {
+        *value = value.checked_add(&Ratio::from(offset))?;
+    }
+    return Ok(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the hailstones of the 2023 day 24 example
+    const HAIL: [([i128; 3], [i128; 3]); 5] = [
+        ([19, 13, 30], [-2, 1, -2]),
+        ([18, 19, 22], [-1, -1, -2]),
+        ([20, 25, 34], [-2, -2, -4]),
+        ([12, 31, 28], [-1, -2, -1]),
+        ([20, 19, 15], [1, -5, -3]),
+    ];
+
+    fn hail() -> Vec<Trajectory> {
+        return HAIL
+            .iter()
+            .map(|&(position, velocity)| Trajectory { position, velocity })
+            .collect();
+    }
+
+    fn r(numer: i128, denom: i128) -> Ratio {
+        return Ratio::new(numer, denom).unwrap();
+    }
+
+    #[test]
+    fn test_solve() {
+        let matrix = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        assert_eq!(
+            Ok(vec![r(2, 1), r(3, 1), r(-1, 1)]),
+            solve_integer(&matrix, &[8, -11, -3])
+        );
+        assert_eq!(
+            Ok(vec![r(1, 3), r(1, 2)]),
+            solve_integer(&[vec![3, 0], vec![0, 4], vec![3, 4]], &[1, 2, 3])
+        );
+        assert_eq!(
+            Err(LinearError::Inconsistent),
+            solve_integer(&[vec![1, 2], vec![2, 4]], &[1, 3])
+        );
+        assert_eq!(
+            Err(LinearError::Underdetermined(1)),
+            solve_integer(&[vec![1, 2], vec![2, 4]], &[1, 2])
+        );
+        assert_eq!(
+            Err(LinearError::Shape),
+            solve_integer(&[vec![1, 2], vec![2]], &[1, 2])
+        );
+    }
+
+    #[test]
+    fn test_intercepting_trajectory() {
+        let rock = intercepting_trajectory(&hail()).unwrap();
+        let expected = [24, 13, 10, -3, 1, 2].map(Ratio::from);
+        assert_eq!(expected, rock);
+        assert_eq!(
+            Err(LinearError::Underdetermined(3)),
+            intercepting_trajectory(&hail()[..2]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // a rock thrown from far away through hailstones with puzzle sized coordinates
+        let rock = Trajectory {
+            position: [191146615936494, 342596108503183, 131079628110881],
+            velocity: [139, -93, 245],
+        };
+        let hail: Vec<Trajectory> = [(1, [-53, 21, -37]), (5, [22, -7, 31]), (11, [-9, 44, 6])]
+            .iter()
+            .map(|&(time, velocity)| {
+                // at `time` the hailstone is where the rock is
+                let position: [i128; 3] = std::array::from_fn(|i| {
+                    rock.position[i] + time * (rock.velocity[i] - velocity[i]) * 1_000_000_000
+                });
+                return Trajectory { position, velocity };
+            })
+            .collect();
+        let solution = intercepting_trajectory(&hail).unwrap();
+        let expected: [Ratio; 6] = std::array::from_fn(|i| {
+            Ratio::from(if i < 3 {
+                rock.position[i]
+            } else {
+                rock.velocity[i - 3]
+            })
+        });
+        assert_eq!(expected, solution);
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    ops::{Add, Div, Mul, Rem, Sub},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MathError {
+    Overflow,
+    NoSolution,
+    NotInvertible,
+    InvalidModulus,
+    DivisionByZero,
+}
+
+impl Display for MathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Self::Overflow => "arithmetic overflow",
+            Self::NoSolution => "no solution",
+            Self::NotInvertible => "not invertible",
+            Self::InvalidModulus => "invalid modulus",
+            Self::DivisionByZero => "division by zero",
+        };
+        return write!(f, "{}", message);
+    }
+}
+
+impl Error for MathError {}
+
+/// Turns the `None` of a `checked_*` operation into `MathError::Overflow`.
+pub fn checked<T>(value: Option<T>) -> Result<T, MathError> {
+    return value.ok_or(MathError::Overflow);
+}
+
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    return <$t>::checked_add(self, rhs);
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    return <$t>::checked_sub(self, rhs);
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    return <$t>::checked_mul(self, rhs);
+                }
+
+                fn checked_neg(self) -> Option<Self> {
+                    return <$t>::checked_neg(self);
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    return <$t>::checked_div(self, rhs);
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    return <$t>::checked_rem(self, rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+fn abs<T: Integer>(value: T) -> Result<T, MathError> {
+    if value < T::ZERO {
+        return value.checked_neg().ok_or(MathError::Overflow);
+    }
+    return Ok(value);
+}
+
+/// The non-negative greatest common divisor, `gcd(0, 0) == 0`.
+///
+/// Fails if the result is not representable, i.e. `gcd(i64::MIN, 0)`.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> Result<T, MathError> {
+    while b != T::ZERO {
+        let t = b;
+        // only `MIN % -1` fails here, and its remainder is 0
+        b = a.checked_rem(b).unwrap_or(T::ZERO);
+        a = t;
+    }
+    return abs(a);
+}
+
+/// The non-negative least common multiple, `lcm(x, 0) == 0`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Result<T, MathError> {
+    if a == T::ZERO || b == T::ZERO {
+        return Ok(T::ZERO);
+    }
+    let g = gcd(a, b)?;
+    let l = checked((a / g).checked_mul(b))?;
+    return abs(l);
+}
+
+/// `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> Result<(T, T, T), MathError> {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        // only `MIN % -1` fails here, and its remainder is 0
+        let r = r0.checked_rem(r1).unwrap_or(T::ZERO);
+        if r == T::ZERO {
+            // the last step, its quotient isn't needed and may not fit
+            (r0, x0, y0) = (r1, x1, y1);
+            break;
+        }
+        let q = r0 / r1;
+        (r0, r1) = (r1, r);
+        let x = checked(x0.checked_sub(checked(q.checked_mul(x1))?))?;
+        let y = checked(y0.checked_sub(checked(q.checked_mul(y1))?))?;
+        (x0, x1) = (x1, x);
+        (y0, y1) = (y1, y);
+    }
+    if r0 < T::ZERO {
+        let neg = |v: T| checked(v.checked_neg());
+        return Ok((neg(r0)?, neg(x0)?, neg(y0)?));
+    }
+    return Ok((r0, x0, y0));
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    assert!(n >= T::ZERO, "square root of a negative number");
+    if n < T::ONE + T::ONE {
+        return n;
+    }
+    // newton's method from above, n / 2 + 1 >= sqrt(n) keeps `x + n / x` from overflowing
+    let two = T::ONE + T::ONE;
+    let mut x = n / two + T::ONE;
+    let mut y = (x + n / x) / two;
+    while y < x {
+        x = y;
+        y = (x + n / x) / two;
+    }
+    return x;
+}
+
+/// `value` reduced into `0..modulus`.
+pub fn rem_euclid<T: Integer>(value: T, modulus: T) -> T {
+    let r = value % modulus;
+    if r < T::ZERO {
+        // r > -modulus, so this can't overflow
+        return r + modulus;
+    }
+    return r;
+}
+
+/// `(a + b) % m` for `a, b` in `0..m`, without ever leaving the range of `T`.
+pub fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        return a - (m - b);
+    }
+    return a + b;
+}
+
+/// `(a - b) % m` for `a, b` in `0..m`.
+pub fn sub_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= b {
+        return a - b;
+    }
+    return m - (b - a);
+}
+
+/// `(a * b) % m` for `a, b` in `0..m`, falling back to double-and-add when the product
+/// doesn't fit into `T`.
+pub fn mul_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let two = T::ONE + T::ONE;
+    let (mut a, mut b) = (a, b);
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / two;
+    }
+    return result;
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`.
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Result<T, MathError> {
+    if m <= T::ZERO {
+        return Err(MathError::InvalidModulus);
+    }
+    // extended euclid, but with the coefficients kept in 0..m so unsigned types work too
+    let (mut r0, mut r1) = (m, rem_euclid(a, m));
+    let (mut t0, mut t1) = (T::ZERO, T::ONE % m);
+    while r1 != T::ZERO {
+        let q = checked(r0.checked_div(r1))?;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, sub_mod(t0, mul_mod(q % m, t1, m), m));
+    }
+    if r0 != T::ONE {
+        return Err(MathError::NotInvertible);
+    }
+    return Ok(t0);
+}
+
+/// Chinese remainder theorem for `x ≡ r (mod m)` for every `(r, m)`, the moduli don't need
+/// to be coprime. Returns `(x, l)` with `x` in `0..l` and `l` the lcm of all moduli.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Result<(T, T), MathError> {
+    let mut x = T::ZERO;
+    let mut l = T::ONE;
+    for &(r, m) in congruences {
+        if m <= T::ZERO {
+            return Err(MathError::InvalidModulus);
+        }
+        let r = rem_euclid(r, m);
+        let g = gcd(l, m)?;
+        let diff = sub_mod(r, x % m, m);
+        if diff % g != T::ZERO {
+            return Err(MathError::NoSolution);
+        }
+
+        // x + l * k ≡ r (mod m)  <=>  (l / g) * k ≡ diff / g (mod m / g)
+        let step = m / g;
+        let k = mul_mod(diff / g % step, mod_inverse(l / g % step, step)?, step);
+        let new_l = (l / g).checked_mul(m).ok_or(MathError::Overflow)?;
+        x = add_mod(x, mul_mod(l, k, new_l), new_l);
+        l = new_l;
+    }
+    return Ok((x, l));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(Ok(6), gcd(48_u32, 18));
+        assert_eq!(Ok(6), gcd(-48_i64, 18));
+        assert_eq!(Ok(0), gcd(0_u8, 0));
+        assert_eq!(Ok(1), gcd(i64::MIN, -1));
+        assert_eq!(Err(MathError::Overflow), gcd(i64::MIN, 0));
+        assert_eq!(Err(MathError::Overflow), gcd(i64::MIN, i64::MIN));
+        assert_eq!(Err(MathError::Overflow), lcm(i64::MIN, i64::MIN));
+        assert_eq!(Ok(805261), lcm(18727_u64, 13201));
+        assert_eq!(Ok(36), lcm(-12_i32, 18));
+        assert_eq!(Err(MathError::Overflow), lcm(u64::MAX, u64::MAX - 1));
+        assert_eq!(Ok(u128::MAX), lcm(u128::MAX, 5));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(0, isqrt(0_u8));
+        assert_eq!(3, isqrt(15_i32));
+        assert_eq!(4, isqrt(16_i32));
+        assert_eq!(u64::MAX as u128, isqrt(u128::MAX));
+        assert_eq!(u32::MAX as u64, isqrt(u64::MAX));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240_i64, 46).unwrap();
+        assert_eq!(2, g);
+        assert_eq!(g, 240 * x + 46 * y);
+        let (g, x, y) = extended_gcd(-35_i32, 15).unwrap();
+        assert_eq!(5, g);
+        assert_eq!(g, -35 * x + 15 * y);
+        assert_eq!(Ok((1, 0, -1)), extended_gcd(i64::MIN, -1));
+        assert_eq!(Ok((1, -1, 0)), extended_gcd(-1, i64::MIN));
+        assert_eq!(Ok((7, 0, 1)), extended_gcd(14_u8, 7));
+        assert_eq!(Ok((0, 1, 0)), extended_gcd(0_i32, 0));
+        assert_eq!(Err(MathError::Overflow), extended_gcd(i64::MIN, 0));
+        let (g, x, y) = extended_gcd(i64::MIN, 3).unwrap();
+        assert_eq!(1, g);
+        assert_eq!(1, i64::MIN as i128 * x as i128 + 3 * y as i128);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(Ok(4), mod_inverse(3_u32, 11));
+        assert_eq!(Ok(7), mod_inverse(-3_i64, 11));
+        assert_eq!(Err(MathError::NotInvertible), mod_inverse(6_u64, 9));
+        let p = (1_u128 << 127) - 1;
+        let inv = mod_inverse(u128::MAX - 7, p).unwrap();
+        assert_eq!(1, mul_mod(inv, (u128::MAX - 7) % p, p));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Ok((23, 105)), crt(&[(2_i64, 3), (3, 5), (2, 7)]));
+        assert_eq!(Ok((10, 12)), crt(&[(4_u32, 6), (2, 4)]));
+        assert_eq!(Err(MathError::NoSolution), crt(&[(1_u32, 6), (2, 4)]));
+        assert_eq!(Err(MathError::Overflow), crt(&[(0_u8, 16), (0, 17)]));
+        let big = crt(&[(1_u128, u64::MAX as u128), (2, u64::MAX as u128 - 1)]).unwrap();
+        assert_eq!(1, big.0 % u64::MAX as u128);
+        assert_eq!(2, big.0 % (u64::MAX as u128 - 1));
+    }
+}
@@ -0,0 +1,143 @@
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    pub entries: usize,
+}
+
+/// A cache for recursive functions. The computation gets the cache passed back in, so it
+/// can recurse through it, and keys may borrow from the input, e.g. `(&[u8], &[usize])`.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        return Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        };
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// The cached value for `key`, or the result of `compute` which is cached from now on.
+    pub fn get_or_compute<F: FnOnce(&mut Self) -> V>(&mut self, key: K, compute: F) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        return value;
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        return self.cache.get(key);
+    }
+
+    /// Forgets every value, but keeps counting hits and misses.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn stats(&self) -> Stats {
+        return Stats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.cache.len(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        return memo.get_or_compute(n, |memo| {
+            return fibonacci(memo, n - 1) + fibonacci(memo, n - 2);
+        });
+    }
+
+    // the number of ways to fill in the `?` of `springs` so the runs of `#` match `groups`
+    fn arrangements<'a>(
+        memo: &mut Memo<(&'a [u8], &'a [usize]), u64>,
+        springs: &'a [u8],
+        groups: &'a [usize],
+    ) -> u64 {
+        let Some((&group, rest)) = groups.split_first() else {
+            return u64::from(!springs.contains(&b'#'));
+        };
+        if springs.len() < group {
+            return 0;
+        }
+        return memo.get_or_compute((springs, groups), |memo| {
+            let mut count = 0;
+            if springs[0] != b'#' {
+                count += arrangements(memo, &springs[1..], groups);
+            }
+            let fits = !springs[..group].contains(&b'.');
+            if fits && springs.len() == group {
+                count += u64::from(rest.is_empty());
+            } else if fits && springs[group] != b'#' {
+                count += arrangements(memo, &springs[group + 1..], rest);
+            }
+            return count;
+        });
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        let mut memo = Memo::new();
+        assert_eq!(12586269025, fibonacci(&mut memo, 50));
+        assert_eq!(
+            Stats {
+                hits: 47,
+                misses: 49,
+                entries: 49
+            },
+            memo.stats()
+        );
+        assert_eq!(Some(&55), memo.get(&10));
+
+        memo.clear();
+        memo.reset_stats();
+        assert_eq!(Stats::default(), memo.stats());
+        assert_eq!(None, memo.get(&10));
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let mut memo = Memo::new();
+        assert_eq!(1, arrangements(&mut memo, b"???.###", &[1, 1, 3]));
+        assert_eq!(4, arrangements(&mut memo, b".??..??...?##.", &[1, 1, 3]));
+        assert_eq!(10, arrangements(&mut memo, b"?###????????", &[3, 2, 1]));
+
+        // unfolded five times, way too many paths without the cache
+        let springs = ["?###????????"; 5].join("?").into_bytes();
+        let groups = [3, 2, 1].repeat(5);
+        let mut memo = Memo::new();
+        assert_eq!(506250, arrangements(&mut memo, &springs, &groups));
+        assert!(memo.stats().hits > 0);
+    }
+}
@@ -1 +1,10 @@
+pub mod cycle;
+pub mod geometry2d;
 pub mod graph;
+pub mod intervals;
+pub mod linear;
+pub mod math;
+pub mod memo;
+pub mod modular;
+pub mod rational;
+pub mod scan;
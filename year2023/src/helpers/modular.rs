@@ -0,0 +1,244 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use super::math::{add_mod, mod_inverse, mul_mod, rem_euclid, sub_mod, MathError};
+
+fn pow_mod(mut base: u128, mut exponent: u128, modulus: u128) -> u128 {
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    return result;
+}
+
+fn reduce_signed(value: i128, modulus: u128) -> u128 {
+    if let Ok(m) = i128::try_from(modulus) {
+        return rem_euclid(value, m) as u128;
+    }
+    // the modulus is above i128::MAX, so one wrap around is enough
+    return if value < 0 {
+        modulus - value.unsigned_abs()
+    } else {
+        value as u128
+    };
+}
+
+/// An integer modulo the compile time constant `M`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Mod<const M: u128> {
+    value: u128,
+}
+
+impl<const M: u128> Mod<M> {
+    const VALID: () = assert!(M > 0, "the modulus has to be positive");
+
+    pub fn new(value: u128) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+        return Self { value: value % M };
+    }
+
+    pub fn from_signed(value: i128) -> Self {
+        return Self::new(reduce_signed(value, M));
+    }
+
+    pub fn value(self) -> u128 {
+        return self.value;
+    }
+
+    pub fn pow(self, exponent: u128) -> Self {
+        return Self::new(pow_mod(self.value, exponent, M));
+    }
+
+    pub fn inverse(self) -> Result<Self, MathError> {
+        return Ok(Self::new(mod_inverse(self.value, M)?));
+    }
+}
+
+impl<const M: u128> Add for Mod<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        return Self::new(add_mod(self.value, rhs.value, M));
+    }
+}
+
+impl<const M: u128> Sub for Mod<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        return Self::new(sub_mod(self.value, rhs.value, M));
+    }
+}
+
+impl<const M: u128> Mul for Mod<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        return Self::new(mul_mod(self.value, rhs.value, M));
+    }
+}
+
+impl<const M: u128> Neg for Mod<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        return Self::new(sub_mod(0, self.value, M));
+    }
+}
+
+impl<const M: u128> Display for Mod<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.value);
+    }
+}
+
+/// An integer modulo a modulus only known at runtime. Mixing different moduli panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynMod {
+    value: u128,
+    modulus: u128,
+}
+
+impl DynMod {
+    pub fn new(value: u128, modulus: u128) -> Result<Self, MathError> {
+        if modulus == 0 {
+            return Err(MathError::InvalidModulus);
+        }
+        return Ok(Self {
+            value: value % modulus,
+            modulus,
+        });
+    }
+
+    pub fn from_signed(value: i128, modulus: u128) -> Result<Self, MathError> {
+        if modulus == 0 {
+            return Err(MathError::InvalidModulus);
+        }
+        return Self::new(reduce_signed(value, modulus), modulus);
+    }
+
+    pub fn value(self) -> u128 {
+        return self.value;
+    }
+
+    pub fn modulus(self) -> u128 {
+        return self.modulus;
+    }
+
+    pub fn pow(self, exponent: u128) -> Self {
+        return self.with(pow_mod(self.value, exponent, self.modulus));
+    }
+
+    pub fn inverse(self) -> Result<Self, MathError> {
+        return Ok(self.with(mod_inverse(self.value, self.modulus)?));
+    }
+
+    fn with(self, value: u128) -> Self {
+        return Self {
+            value,
+            modulus: self.modulus,
+        };
+    }
+
+    fn check(self, other: Self) {
+        assert_eq!(self.modulus, other.modulus, "mixed moduli");
+    }
+}
+
+impl Add for DynMod {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.check(rhs);
+        return self.with(add_mod(self.value, rhs.value, self.modulus));
+    }
+}
+
+impl Sub for DynMod {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.check(rhs);
+        return self.with(sub_mod(self.value, rhs.value, self.modulus));
+    }
+}
+
+impl Mul for DynMod {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.check(rhs);
+        return self.with(mul_mod(self.value, rhs.value, self.modulus));
+    }
+}
+
+impl Neg for DynMod {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        return self.with(sub_mod(0, self.value, self.modulus));
+    }
+}
+
+impl Display for DynMod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} (mod {})", self.value, self.modulus);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type M7 = Mod<1_000_000_007>;
+    // 2^127 - 1 is prime, products of its residues don't fit into u128
+    const MERSENNE: u128 = (1 << 127) - 1;
+
+    #[test]
+    fn test_const_modulus() {
+        let a = M7::new(1_000_000_006);
+        assert_eq!(M7::new(5), a + M7::new(6));
+        assert_eq!(M7::new(1_000_000_005), M7::new(3) - M7::new(5));
+        assert_eq!(M7::new(1), a * a);
+        assert_eq!(a, -M7::new(1));
+        assert_eq!(a, M7::from_signed(-1));
+        assert_eq!(M7::new(1024), M7::new(2).pow(10));
+        // fermat's little theorem
+        let x = M7::new(123_456_789);
+        assert_eq!(x.pow(1_000_000_005), x.inverse().unwrap());
+        assert_eq!(Err(MathError::NotInvertible), Mod::<12>::new(8).inverse());
+
+        let big = Mod::<MERSENNE>::new(u128::MAX);
+        assert_eq!(1, big.value());
+        let x = Mod::<MERSENNE>::new(MERSENNE - 2);
+        assert_eq!(Mod::new(4), x * x);
+        assert_eq!(Mod::new(1), x * x.inverse().unwrap());
+        assert_eq!(x, x.pow(MERSENNE));
+    }
+
+    #[test]
+    fn test_runtime_modulus() {
+        let m = 10007;
+        let a = DynMod::new(10000, m).unwrap();
+        let b = DynMod::from_signed(-10, m).unwrap();
+        assert_eq!(9990, (a + b).value());
+        assert_eq!(3, (a - b).value());
+        assert_eq!(70, (a * b).value());
+        assert_eq!(1, (a * a.inverse().unwrap()).value());
+        assert_eq!(a.pow(m - 2), a.inverse().unwrap());
+        assert_eq!(Err(MathError::InvalidModulus), DynMod::new(1, 0));
+
+        let huge = u128::MAX - 158; // prime
+        let x = DynMod::from_signed(-2, huge).unwrap();
+        assert_eq!(huge - 2, x.value());
+        assert_eq!(4, (x * x).value());
+        assert_eq!(1, (x * x.inverse().unwrap()).value());
+    }
+}
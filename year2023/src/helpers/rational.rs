@@ -0,0 +1,267 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use super::math::{checked, gcd, rem_euclid, Integer, MathError};
+
+/// An exact fraction, always kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio<T = i128> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Integer> Ratio<T> {
+    pub const ZERO: Self = Self {
+        numer: T::ZERO,
+        denom: T::ONE,
+    };
+    pub const ONE: Self = Self {
+        numer: T::ONE,
+        denom: T::ONE,
+    };
+
+    pub fn new(numer: T, denom: T) -> Result<Self, MathError> {
+        if denom == T::ZERO {
+            return Err(MathError::DivisionByZero);
+        }
+        let g = gcd(numer, denom)?;
+        let (mut numer, mut denom) = (numer / g, denom / g);
+        if denom < T::ZERO {
+            numer = checked(numer.checked_neg())?;
+            denom = checked(denom.checked_neg())?;
+        }
+        return Ok(Self { numer, denom });
+    }
+
+    pub fn from_integer(value: T) -> Self {
+        return Self {
+            numer: value,
+            denom: T::ONE,
+        };
+    }
+
+    pub fn numer(&self) -> T {
+        return self.numer;
+    }
+
+    pub fn denom(&self) -> T {
+        return self.denom;
+    }
+
+    pub fn is_integer(&self) -> bool {
+        return self.denom == T::ONE;
+    }
+
+    /// The value as an integer, `None` if it has a fractional part.
+    pub fn to_integer(self) -> Option<T> {
+        return self.is_integer().then_some(self.numer);
+    }
+
+    pub fn floor(&self) -> T {
+        let q = self.numer / self.denom;
+        if self.numer % self.denom < T::ZERO {
+            return q - T::ONE;
+        }
+        return q;
+    }
+
+    pub fn ceil(&self) -> T {
+        let q = self.numer / self.denom;
+        if self.numer % self.denom > T::ZERO {
+            return q + T::ONE;
+        }
+        return q;
+    }
+
+    pub fn checked_neg(&self) -> Result<Self, MathError> {
+        return Ok(Self {
+            numer: checked(self.numer.checked_neg())?,
+            denom: self.denom,
+        });
+    }
+
+    pub fn checked_recip(&self) -> Result<Self, MathError> {
+        return Self::new(self.denom, self.numer);
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Result<Self, MathError> {
+        // a/b + c/d = (a·(d/g) + c·(b/g)) / (b/g·d), keeping the intermediates small
+        let g = gcd(self.denom, other.denom)?;
+        let left = checked(self.numer.checked_mul(other.denom / g))?;
+        let right = checked(other.numer.checked_mul(self.denom / g))?;
+        let denom = checked((self.denom / g).checked_mul(other.denom))?;
+        return Self::new(checked(left.checked_add(right))?, denom);
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, MathError> {
+        return self.checked_add(&other.checked_neg()?);
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, MathError> {
+        // cross cancelling first, both inputs are in lowest terms already
+        let g1 = gcd(self.numer, other.denom)?;
+        let g2 = gcd(other.numer, self.denom)?;
+        let numer = checked((self.numer / g1).checked_mul(other.numer / g2))?;
+        let denom = checked((self.denom / g2).checked_mul(other.denom / g1))?;
+        return Self::new(numer, denom);
+    }
+
+    pub fn checked_div(&self, other: &Self) -> Result<Self, MathError> {
+        return self.checked_mul(&other.checked_recip()?);
+    }
+}
+
+impl<T: Integer> Default for Ratio<T> {
+    fn default() -> Self {
+        return Self::ZERO;
+    }
+}
+
+impl<T: Integer> From<T> for Ratio<T> {
+    fn from(value: T) -> Self {
+        return Self::from_integer(value);
+    }
+}
+
+impl<T: Integer> Ord for Ratio<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // compares the continued fraction expansions, so nothing is ever multiplied
+        let (mut a, mut b) = (*self, *other);
+        let mut reversed = false;
+        loop {
+            let (fa, fb) = (a.floor(), b.floor());
+            if fa != fb {
+                let ordering = fa.cmp(&fb);
+                return if reversed {
+                    ordering.reverse()
+                } else {
+                    ordering
+                };
+            }
+            let ra = rem_euclid(a.numer, a.denom);
+            let rb = rem_euclid(b.numer, b.denom);
+            if ra == T::ZERO || rb == T::ZERO {
+                let ordering = ra.cmp(&rb);
+                return if reversed {
+                    ordering.reverse()
+                } else {
+                    ordering
+                };
+            }
+            // 0 < ra/da < 1, so comparing the reciprocals flips the order
+            (a, b) = (
+                Self {
+                    numer: a.denom,
+                    denom: ra,
+                },
+                Self {
+                    numer: b.denom,
+                    denom: rb,
+                },
+            );
+            reversed = !reversed;
+        }
+    }
+}
+
+impl<T: Integer> PartialOrd for Ratio<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl<T: Integer + Display> Display for Ratio<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            return write!(f, "{}", self.numer);
+        }
+        return write!(f, "{}/{}", self.numer, self.denom);
+    }
+}
+
+// the operators panic on overflow like the primitive ones do in debug builds
+macro_rules! impl_op {
+    ($trait:ident, $method:ident, $checked:ident) => {
+        impl<T: Integer> $trait for Ratio<T> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                return self.$checked(&rhs).expect("ratio arithmetic failed");
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, checked_add);
+impl_op!(Sub, sub, checked_sub);
+impl_op!(Mul, mul, checked_mul);
+impl_op!(Div, div, checked_div);
+
+impl<T: Integer> Neg for Ratio<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        return self.checked_neg().expect("ratio arithmetic failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(numer: i128, denom: i128) -> Ratio {
+        return Ratio::new(numer, denom).unwrap();
+    }
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!((-2, 3), (r(4, -6).numer(), r(4, -6).denom()));
+        assert_eq!(Ratio::ZERO, r(0, -5));
+        assert_eq!(Err(MathError::DivisionByZero), Ratio::new(1, 0));
+        assert_eq!(Err(MathError::Overflow), Ratio::new(i128::MIN, -1));
+        assert_eq!("-2/3", r(4, -6).to_string());
+        assert_eq!(Some(7), r(14, 2).to_integer());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(5, 6), r(1, 2) + r(1, 3));
+        assert_eq!(r(1, 6), r(1, 2) - r(1, 3));
+        assert_eq!(r(-1, 6), r(-1, 2) * r(1, 3));
+        assert_eq!(r(3, 2), r(1, 2) / r(1, 3));
+        assert_eq!(
+            Err(MathError::DivisionByZero),
+            r(1, 2).checked_div(&Ratio::ZERO)
+        );
+
+        let big = r(i128::MAX, 3);
+        assert_eq!(Ratio::ONE, big * big.checked_recip().unwrap());
+        assert_eq!(Err(MathError::Overflow), big.checked_mul(&big));
+        assert_eq!(Err(MathError::Overflow), big.checked_add(&big));
+    }
+
+    #[test]
+    fn test_floor_ceil() {
+        assert_eq!((2, 3), (r(7, 3).floor(), r(7, 3).ceil()));
+        assert_eq!((-3, -2), (r(-7, 3).floor(), r(-7, 3).ceil()));
+        assert_eq!((4, 4), (r(8, 2).floor(), r(8, 2).ceil()));
+        assert_eq!((-1, 0), (r(-1, 5).floor(), r(-1, 5).ceil()));
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(7, 3) > r(9, 4));
+        assert_eq!(Ordering::Equal, r(2, 4).cmp(&r(1, 2)));
+
+        // the cross products would overflow
+        let a = r(i128::MAX - 1, i128::MAX);
+        let b = r(i128::MAX - 2, i128::MAX - 1);
+        assert!(b < a);
+        assert!(-a < -b);
+    }
+}
@@ -0,0 +1,184 @@
+use super::math::Integer;
+
+/// The lines of `input` without their `\n` or `\r\n`, like `str::lines`.
+pub fn lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let empty = input.is_empty();
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+    return input
+        .split(|&b| b == b'\n')
+        .filter(move |_| !empty)
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line));
+}
+
+/// Every digit of `input` read as one number, ignoring everything in between.
+pub fn digits<T: Integer + From<u8>>(input: &[u8]) -> Option<T> {
+    return input
+        .iter()
+        .filter(|b| b.is_ascii_digit())
+        .try_fold(T::ZERO, |acc, &b| {
+            return acc.checked_mul(T::from(10))?.checked_add(T::from(b - b'0'));
+        });
+}
+
+/// A cursor over ASCII input. Numbers skip the spaces and tabs in front of them, but
+/// never a line break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scanner<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        return Self { input, position: 0 };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.position >= self.input.len();
+    }
+
+    pub fn rest(&self) -> &'a [u8] {
+        return &self.input[self.position..];
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        return self.input.get(self.position).copied();
+    }
+
+    pub fn skip_spaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.position += 1;
+        }
+    }
+
+    /// Consumes `token` if the input continues with it, after optional spaces.
+    pub fn token(&mut self, token: &[u8]) -> bool {
+        self.skip_spaces();
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            return true;
+        }
+        return false;
+    }
+
+    /// Everything up to `byte`, moving past it. `None` if there is no `byte` left.
+    pub fn until(&mut self, byte: u8) -> Option<&'a [u8]> {
+        let rest = self.rest();
+        let end = rest.iter().position(|&b| b == byte)?;
+        self.position += end + 1;
+        return Some(&rest[..end]);
+    }
+
+    /// The next number, `None` without a digit or on overflow. Nothing is consumed then.
+    pub fn unsigned<T: Integer + From<u8>>(&mut self) -> Option<T> {
+        let start = self.position;
+        self.skip_spaces();
+        let value = self.digits(T::checked_add);
+        if value.is_none() {
+            self.position = start;
+        }
+        return value;
+    }
+
+    /// Like `unsigned` with an optional `-` or `+` in front.
+    pub fn signed<T: Integer + From<u8>>(&mut self) -> Option<T> {
+        let start = self.position;
+        self.skip_spaces();
+        let negative = self.peek() == Some(b'-');
+        if negative || self.peek() == Some(b'+') {
+            self.position += 1;
+        }
+        // negative numbers are accumulated as such, so `MIN` still fits
+        let value = if negative {
+            self.digits(T::checked_sub)
+        } else {
+            self.digits(T::checked_add)
+        };
+        if value.is_none() {
+            self.position = start;
+        }
+        return value;
+    }
+
+    /// Numbers until something else comes up.
+    pub fn unsigneds<T: Integer + From<u8>>(&mut self) -> impl Iterator<Item = T> + use<'_, 'a, T> {
+        return std::iter::from_fn(|| self.unsigned());
+    }
+
+    pub fn signeds<T: Integer + From<u8>>(&mut self) -> impl Iterator<Item = T> + use<'_, 'a, T> {
+        return std::iter::from_fn(|| self.signed());
+    }
+
+    fn digits<T: Integer + From<u8>, F: Fn(T, T) -> Option<T>>(&mut self, add: F) -> Option<T> {
+        let rest = self.rest();
+        let mut value = T::ZERO;
+        let mut digits = 0;
+        for &b in rest.iter().take_while(|b| b.is_ascii_digit()) {
+            value = add(value.checked_mul(T::from(10))?, T::from(b - b'0'))?;
+            digits += 1;
+        }
+        if digits == 0 {
+            return None;
+        }
+        self.position += digits;
+        return Some(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let collect = |input: &'static [u8]| lines(input).collect::<Vec<_>>();
+        assert_eq!(vec![b"a" as &[u8], b"", b"bc"], collect(b"a\n\nbc\n"));
+        assert_eq!(vec![b"a" as &[u8], b"b"], collect(b"a\r\nb"));
+        assert_eq!(Vec::<&[u8]>::new(), collect(b""));
+        assert_eq!(vec![b"" as &[u8]], collect(b"\n"));
+        let text = "x\n\ny z\r\n\n";
+        assert_eq!(
+            text.lines().map(str::as_bytes).collect::<Vec<_>>(),
+            collect(text.as_bytes())
+        );
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(Some(71530_u64), digits(b"Time:      7  15   30"));
+        assert_eq!(Some(0_u8), digits(b"none"));
+        assert_eq!(None, digits::<u8>(b"2 5 6"));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let mut scanner = Scanner::new(b"Card  1: 41 48 | 83 -86\n2");
+        assert_eq!(Some(b"Card  1" as &[u8]), scanner.until(b':'));
+        assert_eq!(vec![41, 48], scanner.unsigneds::<u32>().collect::<Vec<_>>());
+        assert!(!scanner.token(b"Card"));
+        assert!(scanner.token(b"|"));
+        assert_eq!(Some(83_u8), scanner.unsigned());
+        assert_eq!(None, scanner.unsigned::<u8>());
+        assert_eq!(Some(b' '), scanner.peek());
+        assert_eq!(Some(-86_i16), scanner.signed());
+        // line breaks stop the numbers
+        assert_eq!(None, scanner.signed::<i16>());
+        assert!(scanner.token(b"\n"));
+        assert_eq!(Some(2_u8), scanner.unsigned());
+        assert!(scanner.is_empty());
+        assert_eq!(None, scanner.until(b':'));
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut scanner = Scanner::new(b"-32768 +32767 32768 256 255");
+        let signed: Vec<i16> = scanner.signeds().collect();
+        assert_eq!(vec![-32768, 32767], signed);
+        assert_eq!(b" 32768 256 255", scanner.rest());
+        assert_eq!(Some(32768_i32), scanner.signed());
+        assert_eq!(None, scanner.unsigned::<u8>());
+        assert!(scanner.token(b"256"));
+        assert_eq!(Some(255_u8), scanner.unsigned());
+        assert!(scanner.is_empty());
+    }
+}
@@ -3,10 +3,10 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
-mod helpers;
+pub mod helpers;
 
 mod day01;
-mod day02;
+pub mod day02;
 mod day03;
 mod day04;
 mod day05;
@@ -22,4 +22,7 @@ mod day14;
 
 mod day16;
 
+#[cfg(feature = "compare")]
+pub mod compare;
+
 aoc_lib! { year = 2023 }